@@ -12,19 +12,17 @@ fn get_commit_hash(len: Option<u8>) -> Option<String> {
             &format!("commit_id.short({})", hash_len),
         ])
         .output()
+        && output.status.success()
     {
-        if output.status.success() {
-            return Some(String::from_utf8(output.stdout).unwrap());
-        }
+        return Some(String::from_utf8(output.stdout).unwrap());
     }
 
     if let Ok(output) = std::process::Command::new("git")
         .args(["rev-parse", &format!("--short={}", hash_len), "HEAD"])
         .output()
+        && output.status.success()
     {
-        if output.status.success() {
-            return Some(String::from_utf8(output.stdout).unwrap());
-        }
+        return Some(String::from_utf8(output.stdout).unwrap());
     }
 
     None
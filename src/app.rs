@@ -1,53 +1,86 @@
 use std::{
     borrow::Cow,
-    cmp::{max, min},
+    cmp::{Reverse, max, min},
+    collections::{BTreeMap, HashMap, HashSet},
+    env,
     error::Error,
-    hash::{DefaultHasher, Hash, Hasher},
+    io,
     num::{NonZero, NonZeroU64},
-    path::PathBuf,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    process::Stdio,
     sync::{
-        Arc, RwLock,
+        Arc, Mutex, RwLock,
         atomic::{AtomicUsize, Ordering},
     },
-    time::Duration,
+    time::{Duration, Instant},
     vec,
 };
 
-use chrono::DateTime;
+use arboard::Clipboard;
+use atom_syndication::TextType;
+use chrono::{DateTime, TimeDelta, Utc};
 use chrono_humanize::HumanTime;
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
-use itertools::chain;
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use itertools::{Itertools, chain};
 use ratatui::{
     Frame, Terminal,
-    layout::{Flex, Layout, Margin, Rect},
+    buffer::Buffer,
+    layout::{Flex, Layout, Margin, Position, Rect},
     prelude::Backend,
     style::{Color, Stylize},
-    text::{Line, Text},
+    text::{Line, Span, Text},
     widgets::{
-        Block, BorderType, HighlightSpacing, Padding, Row, Scrollbar, ScrollbarOrientation,
-        ScrollbarState, Table, TableState, Widget,
+        Block, BorderType, Borders, Clear, HighlightSpacing, Padding, Paragraph, Row, Scrollbar,
+        ScrollbarOrientation, ScrollbarState, Table, TableState, Widget,
     },
 };
+use ratatui_image::picker::Picker;
 use ratatui_macros::{constraints, horizontal, line, row, span, text, vertical};
-use reqwest::Client;
+use reqwest::{
+    Client, NoProxy, Proxy, RequestBuilder, Response, StatusCode,
+    header::{ACCEPT, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    redirect::Policy,
+};
 use tokio::{
     fs,
-    sync::mpsc::{Receiver, Sender},
+    sync::{
+        Mutex as AsyncMutex, Notify, Semaphore,
+        mpsc::{Receiver, Sender},
+    },
     task::JoinSet,
 };
 use tokio_stream::StreamExt;
 use url::Url;
 
 use crate::{
+    bookmarks::{read_bookmarks, toggle_bookmark},
+    config::{Config, SourceLabels, View},
     event::AppEvent,
+    feeds::{parse_feeds_list, read_feeds_file},
+    http_cache::HttpCache,
+    images::{ImageCache, alt_text, parse_image_line, resolve_src, without_image},
+    jsonfeed::{JsonFeed, JsonFeedItem},
+    keys::{Action, Key},
+    opml::{self, OpmlFeed},
     para_wrap,
-    stream::RateLimitedEventStream,
-    utils::{LONG_TIMESTAMP_FMT, Throbber, WARM_WHITE_RGB, try_parse_html, wrap_then_apply},
+    state::State,
+    stream::{RateLimitedEventStream, RateLimits},
+    utils::{
+        Hyperlink, SHORT_TIMESTAMP_FMT, Theme, Throbber, WrappedUrl, body_preview, download_to_dir,
+        draw_hyperlink, find_wrapped_urls, footnote_links, format_size, gunzip_body,
+        is_html_document, is_xml_document, normalize_url, parse_feed_date, pipe_through_command,
+        shell_quote, stable_hash, strip_query_params, truncate_then_apply, try_parse_html,
+        wrap_indented_then_apply, wrap_or_truncate_then_apply, wrap_then_apply,
+    },
 };
 
-use crate::debug::FpsWidget;
+use crate::debug::{FpsWidget, PerfLog};
 
 pub struct App {
+    config: Arc<Config>,
     // app state
     should_quit: bool,
     // widgets
@@ -55,86 +88,256 @@ pub struct App {
     feed: FeedWidget,
     // perf/debug widgets
     fps: Option<FpsWidget>,
+    perf_log: Option<PerfLog>,
+    // Initialised on first use, and kept alive as some platforms (e.g. X11) only serve the clipboard
+    // contents for as long as the owner is alive
+    clipboard: Option<Clipboard>,
+    // Whether the overlay listing every action and its keys is shown (over the active view)
+    show_key_help: bool,
+    // Whether the prompt asking to confirm quitting is shown, after closing at the top level (e.g. `q` in
+    // the list) or exiting with `--confirm-quit`
+    confirm_quit: bool,
 
     app_event_rx: Receiver<AppEvent>,
 }
 
-impl Default for App {
-    fn default() -> Self {
+impl App {
+    const UNCAPPED_TICK_RATE: Duration = Duration::from_millis(250);
+
+    pub(crate) fn new(
+        config: Config,
+        image_picker: Option<Picker>,
+        perf_log: Option<PerfLog>,
+    ) -> Self {
+        let config = Arc::new(config);
         let (app_event_tx, app_event_rx) = tokio::sync::mpsc::channel(1);
         Self {
             should_quit: false,
             throbber: Throbber::new(Duration::from_millis(250)),
-            feed: FeedWidget::new(app_event_tx.clone()),
-            fps: None,
+            feed: FeedWidget::new(app_event_tx.clone(), Arc::clone(&config), image_picker),
+            fps: config.show_fps.then(FpsWidget::default),
+            perf_log,
+            clipboard: None,
+            show_key_help: false,
+            confirm_quit: false,
             app_event_rx,
+            config,
         }
     }
-}
 
-impl App {
-    pub async fn run<B: Backend>(
+    pub async fn run<B: Backend + io::Write>(
         mut self,
         terminal: &mut Terminal<B>,
-        feeds_file: PathBuf,
-        tick_rate: Duration,
-        show_fps: bool,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        if show_fps {
-            self.fps = Some(FpsWidget::default());
+        let feeds_list = match self.config.feeds_url {
+            Some(ref feeds_url) => self
+                .fetch_feeds_list(feeds_url)
+                .await
+                .map(|content| parse_feeds_list(&content)),
+            None => read_feeds_file(&self.config.feeds_file),
         }
+        .unwrap_or_default();
+        // Included files may share feeds
+        let feed_urls = feeds_list.urls.into_iter().unique().collect();
 
-        let feed_urls = fs::read_to_string(feeds_file)
-            .await
-            .map(|content| {
-                content
-                    .lines()
-                    .map(str::trim)
-                    .filter_map(|line| {
-                        if !line.is_empty() {
-                            Url::parse(line).ok().map(|url| url.to_string())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect::<Vec<_>>()
-            })
-            .unwrap_or_default();
-
-        self.feed.run(feed_urls);
-
-        let mut tick_rate = tokio::time::interval(tick_rate);
+        self.feed.load_state(State::load(&self.config.state_file));
+        self.feed.load_bookmarks();
+        self.feed.spawn_state_writer();
+        self.feed.run(feed_urls, feeds_list.groups);
+        // Skipped includes are listed by `rssterm list`, as the footer only has room for one of them
+        match feeds_list.skipped_includes.as_slice() {
+            [] => {}
+            [skipped_include] => self.feed.set_status(format!("Skipped {}", skipped_include)),
+            skipped_includes => self.feed.set_status(format!(
+                "Skipped {} @include lines, see `rssterm list`",
+                skipped_includes.len()
+            )),
+        }
 
-        /*
-         Currently, only scroll events (up/down/mouse scroll) are rate-limited to 15ms.
-         The logic for determining whether an event should be rate-limited is in the `RateLimitedEventStream`.
+        // When uncapped, frames are drawn as soon as something changes (input or feed data) instead of on
+        // a near-zero interval, and the interval only keeps time-based elements (clock, throbber) moving
+        let uncapped = self.config.tick_rate.is_none();
+        let mut tick_rate =
+            tokio::time::interval(self.config.tick_rate.unwrap_or(Self::UNCAPPED_TICK_RATE));
+        let redraw = self.feed.redraw_signal();
+        // The first refresh is one interval after launch, as the feeds are being fetched at launch. A
+        // refresh that is due while feeds are still loading is skipped
+        let mut auto_refresh = self.config.refresh_interval.map(|period| {
+            let mut interval =
+                tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            interval
+        });
 
-         Delay of 15ms maintains smooth scrolling (1s/15ms = 66.67 FPS) while preventing event flooding
-         from high-sensitivity mice (e.g. MX Master's fast scroll wheel).
-        */
-        let mut term_events = RateLimitedEventStream::new(Duration::from_millis(15));
+        let mut term_events = RateLimitedEventStream::new(RateLimits::default());
 
         while !self.should_quit {
             tokio::select! {
                 biased;
-                Some(Ok(term_event)) = term_events.next() => self.handle_term_event(&term_event).await,
-                Some(AppEvent::Exit) = self.app_event_rx.recv() => self.should_quit = true,
-                _ = tick_rate.tick() => { terminal.draw(|frame| self.draw(frame))?; }
+                Some(Ok(term_event)) = term_events.next() => {
+                    self.handle_term_event(&term_event).await;
+                    if uncapped && !self.should_quit {
+                        self.draw_frame(terminal, || match &term_event {
+                            Event::Key(key) => format!("key {}", Key::from(key)),
+                            _ => "input".to_string(),
+                        })?;
+                    }
+                }
+                Some(app_event) = self.app_event_rx.recv() => {
+                    match app_event {
+                        AppEvent::Exit => self.should_quit = true,
+                        AppEvent::ConfirmQuit => self.confirm_quit = true,
+                        _ => (),
+                    }
+                    if uncapped && !self.should_quit {
+                        self.draw_frame(terminal, || "app event".to_string())?;
+                    }
+                }
+                _ = redraw.notified(), if uncapped => {
+                    self.draw_frame(terminal, || "data".to_string())?;
+                }
+                _ = tick_rate.tick() => { self.draw_frame(terminal, || "tick".to_string())?; }
+                _ = async { auto_refresh.as_mut().unwrap().tick().await }, if auto_refresh.is_some() => {
+                    self.feed.refresh();
+                }
             }
         }
 
+        // Failing to save the state must not keep the terminal from being restored
+        let saved = FeedWidget::save_state(
+            &self.feed.data,
+            &self.config.state_file,
+            &self.feed.state_write_lock,
+        )
+        .await;
+        if let Err(e) = saved {
+            eprintln!("Failed to save state: {}", e);
+        }
+
+        Ok(())
+    }
+
+    // Draw a frame, timing it with `--perf-log`, where `trigger` describes what caused the frame (e.g. the
+    // key pressed) and is only called when the frame is logged
+    fn draw_frame<B: Backend + io::Write>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        trigger: impl FnOnce() -> String,
+    ) -> io::Result<()> {
+        if self.perf_log.is_none() {
+            let completed_frame = terminal.draw(|frame| self.draw(frame))?;
+            let hyperlinks = self.hyperlink_cells(completed_frame.buffer);
+            return self.draw_hyperlinks(terminal, hyperlinks);
+        }
+
+        let frame_start = Instant::now();
+        let mut render = Duration::ZERO;
+        let completed_frame = terminal.draw(|frame| {
+            self.draw(frame);
+            render = frame_start.elapsed();
+        })?;
+        let hyperlinks = self.hyperlink_cells(completed_frame.buffer);
+        self.draw_hyperlinks(terminal, hyperlinks)?;
+        let draw = frame_start.elapsed();
+        if let Some(perf_log) = &mut self.perf_log
+            && let Err(e) = perf_log.record(frame_start, render, draw, &trigger())
+        {
+            eprintln!("Failed to write perf log: {}", e);
+            self.perf_log = None;
+        }
         Ok(())
     }
 
+    // Cells of the links of the drawn frame, by URL, unless hyperlinks are turned off (`--no-hyperlinks`)
+    // or the links are covered by an overlay
+    fn hyperlink_cells(&self, buffer: &Buffer) -> Vec<Hyperlink> {
+        if !self.config.hyperlinks || self.show_key_help || self.confirm_quit {
+            return Vec::new();
+        }
+        (self.feed.hyperlinks().iter())
+            .map(|(area, url)| {
+                // The cells covered by a wide character (i.e. the cells after it) are not drawn, as ratatui
+                // does not draw them either
+                let mut covered = 0;
+                let cells = (area.positions())
+                    .filter_map(|position| buffer.cell(position).map(|cell| (position, cell)))
+                    .filter(|(_, cell)| match covered {
+                        0 => {
+                            covered = Span::raw(cell.symbol()).width().saturating_sub(1);
+                            true
+                        }
+                        _ => {
+                            covered -= 1;
+                            false
+                        }
+                    })
+                    .map(|(position, cell)| (position.x, position.y, cell.clone()))
+                    .collect();
+                Hyperlink {
+                    url: url.clone(),
+                    cells,
+                }
+            })
+            .collect()
+    }
+
+    fn draw_hyperlinks<B: Backend + io::Write>(
+        &self,
+        terminal: &mut Terminal<B>,
+        hyperlinks: Vec<Hyperlink>,
+    ) -> io::Result<()> {
+        if hyperlinks.is_empty() {
+            return Ok(());
+        }
+        let backend = terminal.backend_mut();
+        for hyperlink in &hyperlinks {
+            draw_hyperlink(backend, hyperlink)?;
+        }
+        Backend::flush(backend)
+    }
+
+    // Download the feeds list from a remote URL, caching it locally so that the last fetched list can
+    // still be used when offline
+    async fn fetch_feeds_list(&self, feeds_url: &str) -> Option<String> {
+        let cache_file = &self.config.feeds_url_cache_file;
+        match self.feed.fetch_text(feeds_url).await {
+            Ok(content) => {
+                if let Some(cache_dir) = cache_file.parent() {
+                    fs::create_dir_all(cache_dir).await.ok();
+                }
+                if let Err(e) = fs::write(cache_file, &content).await {
+                    eprintln!("Failed to cache feeds list: {}", e);
+                }
+                Some(content)
+            }
+            Err(e) => {
+                eprintln!(
+                    "Failed to fetch feeds list ({}), using cached list: {}",
+                    feeds_url, e
+                );
+                fs::read_to_string(cache_file).await.ok()
+            }
+        }
+    }
+
     async fn handle_term_event(&mut self, event: &Event) {
         let app_event = match event {
             Event::Key(key) => self.parse_term_key_event(key),
+            Event::Mouse(mouse) => self.parse_term_mouse_event(mouse),
             _ => None,
         };
 
         if let Some(app_event) = app_event {
             match app_event {
+                AppEvent::Exit if self.config.confirm_quit && !self.confirm_quit => {
+                    self.confirm_quit = true
+                }
                 AppEvent::Exit => self.should_quit = true,
+                AppEvent::CancelQuit => self.confirm_quit = false,
+                AppEvent::ToggleKeyHelp => self.show_key_help = !self.show_key_help,
+                AppEvent::CopyUrl => self.copy_selected_url(),
+                AppEvent::CopyLines => self.copy_selected_lines(),
+                AppEvent::CopyDebugStats => self.copy_debug_stats(),
                 // Since there is only one active widget (`FeedWidget`), we can directly dispatch all
                 // non-exit events to it. When more widgets are added, we will need to identify which
                 // widget is active and dispatch the event accordingly.
@@ -148,22 +351,321 @@ impl App {
         if key_event.kind != KeyEventKind::Press {
             return None;
         }
+        // The quit prompt takes every key, where only y (quit), n and Esc (cancel) answer it
+        if self.confirm_quit {
+            return match key_event.code {
+                KeyCode::Char('y' | 'Y') => Some(AppEvent::Exit),
+                KeyCode::Char('n' | 'N') | KeyCode::Esc => Some(AppEvent::CancelQuit),
+                _ => None,
+            };
+        }
+        // The help overlay takes every key, and is closed with the keys that close views, without exiting
+        if self.show_key_help {
+            return match (key_event.modifiers, key_event.code) {
+                (_, KeyCode::Char('?') | KeyCode::Esc) => Some(AppEvent::ToggleKeyHelp),
+                _ => match self.config.keys.action(key_event) {
+                    Some(Action::Close) => Some(AppEvent::ToggleKeyHelp),
+                    Some(Action::Exit) => Some(AppEvent::Exit),
+                    _ => None,
+                },
+            };
+        }
+        if self.feed.is_searching() {
+            return Self::parse_search_key_event(key_event);
+        }
+        // Keys bound in the keys file take precedence over the keys of actions that cannot be rebound
+        if let Some(action) = self.config.keys.action(key_event) {
+            return Some(action.app_event());
+        }
         match (key_event.modifiers, key_event.code) {
-            (_, KeyCode::Up | KeyCode::Char('k')) => Some(AppEvent::Scroll(-1)),
-            (_, KeyCode::Down | KeyCode::Char('j')) => Some(AppEvent::Scroll(1)),
-            (_, KeyCode::Char('g')) => Some(AppEvent::Scroll(isize::MIN)),
-            (KeyModifiers::SHIFT, KeyCode::Char('G')) => Some(AppEvent::Scroll(isize::MAX)),
+            (_, KeyCode::Backspace) => Some(AppEvent::Back),
+            (_, KeyCode::Char('/')) => Some(AppEvent::Search),
+            (_, KeyCode::Char('?')) => Some(AppEvent::ToggleKeyHelp),
+            (_, KeyCode::Esc) => Some(AppEvent::Close),
 
-            (_, KeyCode::Enter) => Some(AppEvent::Expand),
-            (_, KeyCode::Char('q')) => Some(AppEvent::Close),
+            (KeyModifiers::NONE, KeyCode::Char(digit @ '1'..='9')) => {
+                Some(AppEvent::OpenLink(digit as usize - '0' as usize))
+            }
+            (_, KeyCode::Char('t')) => Some(AppEvent::Translate),
+            (_, KeyCode::Char('f')) => Some(AppEvent::FetchContent),
+            (KeyModifiers::SHIFT, KeyCode::Char('S')) => Some(AppEvent::Summarize),
+            (_, KeyCode::Tab) => Some(AppEvent::ToggleSidebar),
+            (_, KeyCode::Char('c')) => Some(AppEvent::ToggleGroup),
+            (_, KeyCode::Char('r')) => Some(AppEvent::Refresh),
+            (_, KeyCode::Char('y')) if self.feed.selected_line_count().is_some() => {
+                Some(AppEvent::CopyLines)
+            }
+            (_, KeyCode::Char('y')) => Some(AppEvent::CopyUrl),
+            (KeyModifiers::NONE, KeyCode::Char('v')) => Some(AppEvent::ToggleLineSelection),
+            (KeyModifiers::SHIFT, KeyCode::Char('O')) => Some(AppEvent::OpenSite),
+            (KeyModifiers::NONE, KeyCode::Char('p')) => Some(AppEvent::OpenEnclosure),
+            (KeyModifiers::NONE, KeyCode::Char('w')) => Some(AppEvent::DownloadEnclosure),
+            (KeyModifiers::NONE, KeyCode::Char('b')) => Some(AppEvent::Bookmark),
+            (KeyModifiers::NONE, KeyCode::Char('x')) => Some(AppEvent::SendItem),
+            (KeyModifiers::NONE, KeyCode::Char('d')) => Some(AppEvent::ToggleDates),
+            (KeyModifiers::NONE, KeyCode::Char('s')) => Some(AppEvent::CycleSort),
+            (KeyModifiers::NONE, KeyCode::Char('a')) => Some(AppEvent::MarkAllRead),
+            (KeyModifiers::NONE, KeyCode::Char('u')) => Some(AppEvent::ToggleRead),
+            (KeyModifiers::SHIFT, KeyCode::Char('U')) => Some(AppEvent::ToggleUnreadOnly),
+            (KeyModifiers::SHIFT, KeyCode::Char('L')) => Some(AppEvent::ToggleUrls),
+            (_, KeyCode::Char('!')) => Some(AppEvent::ToggleErrors),
+            (KeyModifiers::SHIFT, KeyCode::Char('E')) => Some(AppEvent::ExportOpml),
+            (KeyModifiers::SHIFT, KeyCode::Char('D')) if self.fps.is_some() => {
+                Some(AppEvent::CopyDebugStats)
+            }
+            _ => None,
+        }
+    }
 
-            (_, KeyCode::Char('o')) => Some(AppEvent::Open),
+    // Map terminal mouse events (with `--mouse`) to app events, where clicking a row of the list selects it
+    // and clicking the selected row (e.g. the second click of a double click) expands it
+    fn parse_term_mouse_event(&self, mouse_event: &MouseEvent) -> Option<AppEvent> {
+        // The quit prompt and the help overlay are answered and closed with keys only
+        if self.confirm_quit || self.show_key_help {
+            return None;
+        }
+        match mouse_event.kind {
+            MouseEventKind::ScrollUp => Some(AppEvent::Scroll(-1)),
+            MouseEventKind::ScrollDown => Some(AppEvent::Scroll(1)),
+            MouseEventKind::Down(MouseButton::Left) if !self.feed.is_searching() => {
+                let row_i = self
+                    .feed
+                    .row_at(Position::new(mouse_event.column, mouse_event.row))?;
+                match self.feed.tb_state.selected() == Some(row_i) {
+                    true => Some(AppEvent::Expand),
+                    false => Some(AppEvent::Select(row_i)),
+                }
+            }
+            _ => None,
+        }
+    }
 
-            (KeyModifiers::CONTROL, KeyCode::Char('d')) => Some(AppEvent::Exit),
+    // While typing a search query, keys are part of the query, except for those that end the search,
+    // scroll (so the matching items can be browsed while typing) and exit
+    fn parse_search_key_event(key_event: &KeyEvent) -> Option<AppEvent> {
+        match (key_event.modifiers, key_event.code) {
+            (KeyModifiers::CONTROL, KeyCode::Char('c')) => Some(AppEvent::Exit),
+            (_, KeyCode::Esc) => Some(AppEvent::SearchClear),
+            (_, KeyCode::Enter) => Some(AppEvent::SearchSubmit),
+            (_, KeyCode::Backspace) => Some(AppEvent::SearchDelete),
+            (_, KeyCode::Up) => Some(AppEvent::Scroll(-1)),
+            (_, KeyCode::Down) => Some(AppEvent::Scroll(1)),
+            (_, KeyCode::PageUp) => Some(AppEvent::ScrollPage(-2)),
+            (_, KeyCode::PageDown) => Some(AppEvent::ScrollPage(2)),
+            (modifiers, KeyCode::Char(c)) if (modifiers - KeyModifiers::SHIFT).is_empty() => {
+                Some(AppEvent::SearchInput(c))
+            }
             _ => None,
         }
     }
 
+    fn copy_to_clipboard(&mut self, text: String) -> Result<(), arboard::Error> {
+        let clipboard = match self.clipboard {
+            Some(ref mut clipboard) => clipboard,
+            None => self.clipboard.insert(Clipboard::new()?),
+        };
+        clipboard.set_text(text)
+    }
+
+    // Copy the URL of the selected (or expanded) item
+    fn copy_selected_url(&mut self) {
+        let Some(url) = self.feed.selected_url() else {
+            self.feed
+                .set_status("No item selected or no URL available".to_string());
+            return;
+        };
+        let status = match self.copy_to_clipboard(url) {
+            Ok(()) => "Copied URL to clipboard".to_string(),
+            Err(e) => format!("Failed to copy URL: {}", e),
+        };
+        self.feed.set_status(status);
+    }
+
+    // Copy the lines selected in the expanded item, which ends the selection
+    fn copy_selected_lines(&mut self) {
+        let Some(lines) = self.feed.take_selected_lines() else {
+            return;
+        };
+        let status = match self.copy_to_clipboard(lines.join("\n")) {
+            Ok(()) => format!(
+                "Copied {} line{} to clipboard",
+                lines.len(),
+                if lines.len() == 1 { "" } else { "s" }
+            ),
+            Err(e) => format!("Failed to copy lines: {}", e),
+        };
+        self.feed.set_status(status);
+    }
+
+    // Copy the stats shown by the FPS widget along with the feed and terminal size as a single line, for
+    // pasting into performance reports
+    fn copy_debug_stats(&mut self) {
+        let Some(fps_widget) = &self.fps else {
+            return;
+        };
+        let (fps, frame_time) = match fps_widget.fps() {
+            Some(fps) => (format!("{:.2}", fps), format!("{:.2}", 1000.0 / fps)),
+            None => ("n/a".to_string(), "n/a".to_string()),
+        };
+        let (term_w, term_h) = crossterm::terminal::size().unwrap_or_default();
+        let stats = format!(
+            "{} v{} | {} fps | {} ms/frame | {} items | {}x{}",
+            env!("CARGO_PKG_NAME"),
+            env!("RSSTERM_VERSION"),
+            fps,
+            frame_time,
+            self.feed.item_count(),
+            term_w,
+            term_h
+        );
+
+        let status = match self.copy_to_clipboard(stats) {
+            Ok(()) => "Copied debug stats to clipboard".to_string(),
+            Err(e) => format!("Failed to copy debug stats: {}", e),
+        };
+        self.feed.set_status(status);
+    }
+
+    // Keys of every action (along with a short description), as shown in the footer and the help overlay.
+    // Rebindable actions show their bound keys, and actions that are unavailable are left out
+    fn key_hints(&self) -> Vec<(String, &'static str)> {
+        let keys = &self.config.keys;
+        [
+            Some((keys.hint(&[Action::ScrollDown, Action::ScrollUp]), "scroll")),
+            Some((
+                keys.hint(&[Action::HalfPageDown, Action::HalfPageUp]),
+                "half page",
+            )),
+            Some((keys.hint(&[Action::PageDown, Action::PageUp]), "page")),
+            Some((keys.hint(&[Action::Top, Action::Bottom]), "top/btm")),
+            Some((keys.hint(&[Action::Expand]), "expand")),
+            Some((keys.hint(&[Action::Open]), "open")),
+            Some(("O".to_string(), "open site")),
+            Some(("y".to_string(), "copy url")),
+            Some(("v".to_string(), "select lines")),
+            Some(("b".to_string(), "bookmark")),
+            self.config
+                .send_cmd
+                .as_ref()
+                .map(|_| ("x".to_string(), "send")),
+            Some(("r".to_string(), "refresh")),
+            Some(("Tab".to_string(), "sources")),
+            Some(("/".to_string(), "search")),
+            self.feed
+                .has_groups()
+                .then(|| ("c".to_string(), "collapse")),
+            Some(("d".to_string(), "dates")),
+            Some(("a".to_string(), "all read")),
+            Some(("u".to_string(), "unread")),
+            Some(("U".to_string(), "unread only")),
+            Some(("L".to_string(), "urls")),
+            Some(("s".to_string(), "sort")),
+            (self.feed.failed_count() + self.feed.moved_count() > 0)
+                .then(|| ("!".to_string(), "errors")),
+            Some(("E".to_string(), "export")),
+            Some(("1-9".to_string(), "open link")),
+            Some(("f".to_string(), "fetch content")),
+            Some(("p".to_string(), "play enclosure")),
+            Some(("w".to_string(), "download enclosure")),
+            self.config
+                .translate_cmd
+                .as_ref()
+                .map(|_| ("t".to_string(), "translate")),
+            self.config
+                .summarize_cmd
+                .as_ref()
+                .map(|_| ("S".to_string(), "tl;dr")),
+            self.fps.as_ref().map(|_| ("D".to_string(), "copy stats")),
+            Some((keys.hint(&[Action::Close]), "close")),
+            Some(("⌫".to_string(), "back")),
+            Some(("?".to_string(), "help")),
+            Some((keys.hint(&[Action::Exit]), "exit")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    // Overlay listing every action and its keys, centered over `area`, in as many columns as needed for
+    // the list to fit its height
+    fn render_key_help(&self, frame: &mut Frame, area: Rect) {
+        let key_hints = self.key_hints();
+        let key_w = key_hints
+            .iter()
+            .map(|(key, _)| key.chars().count())
+            .max()
+            .unwrap_or(0);
+        let desc_w = key_hints
+            .iter()
+            .map(|(_, desc)| desc.chars().count())
+            .max()
+            .unwrap_or(0);
+        // +2 for the borders, and +2 for the padding (i.e. a blank line above and below the list)
+        let max_rows = area.height.saturating_sub(4).max(1) as usize;
+        let column_count = key_hints.len().div_ceil(max_rows).max(1);
+        let rows = key_hints.len().div_ceil(column_count);
+
+        let column_gap = 4;
+        let column_w = key_w + 2 + desc_w;
+        let lines: Vec<Line> = (0..rows)
+            .map(|row| {
+                let mut spans = Vec::new();
+                for (i, (key, desc)) in key_hints.iter().skip(row).step_by(rows).enumerate() {
+                    if i > 0 {
+                        spans.push(span!(" ".repeat(column_gap)));
+                    }
+                    spans.push(
+                        span!("{:>key_w$}", key)
+                            .fg(self.config.theme.highlight)
+                            .bold(),
+                    );
+                    spans.push(span!("  {:desc_w$}", desc).fg(self.config.theme.content));
+                }
+                Line::from(spans)
+            })
+            .collect();
+
+        // +2 for the borders, and +4 for the padding
+        let overlay_w = (column_count * column_w + (column_count - 1) * column_gap) as u16 + 6;
+        let overlay_h = rows as u16 + 4;
+        let [overlay_area] = horizontal![==overlay_w].flex(Flex::Center).areas(area);
+        let [overlay_area] = vertical![==overlay_h]
+            .flex(Flex::Center)
+            .areas(overlay_area);
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .title(line!(" Keys ").bold())
+            .title_bottom(
+                line!(format!(
+                    " ?/Esc/{} close ",
+                    self.config.keys.hint(&[Action::Close])
+                ))
+                .dim()
+                .right_aligned(),
+            )
+            .padding(Padding::symmetric(2, 1));
+        frame.render_widget(Clear, overlay_area);
+        frame.render_widget(Paragraph::new(lines).block(block), overlay_area);
+    }
+
+    // Small modal centered over `area`, asking to confirm quitting
+    fn render_quit_prompt(frame: &mut Frame, area: Rect) {
+        let prompt = line![span!("Quit? ").bold(), span!("(y/n)").dim()];
+        // +2 for the borders, and +4 for the padding
+        let [prompt_area] = horizontal![==prompt.width() as u16 + 6]
+            .flex(Flex::Center)
+            .areas(area);
+        let [prompt_area] = vertical![==3].flex(Flex::Center).areas(prompt_area);
+
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::Yellow)
+            .padding(Padding::horizontal(2));
+        frame.render_widget(Clear, prompt_area);
+        frame.render_widget(Paragraph::new(prompt).block(block), prompt_area);
+    }
+
     fn draw(&mut self, frame: &mut Frame) {
         let fps_widget_h = if self.fps.is_some() { 1 } else { 0 };
         let [header_area, main_area, _, footer_area, _, fps_area] =
@@ -176,7 +678,36 @@ impl App {
         let app_version = format!("v{}", env!("RSSTERM_VERSION"));
         let title_len = (app_name.len() + app_version.len() + 1) as u16; // +1 for space
 
-        let [title_area, _, throbber_area] = horizontal![==title_len, ==1, ==1].areas(h_left_area);
+        let failed_count = self.feed.failed_count();
+        let moved_count = self.feed.moved_count();
+        let issues_label = Line::from_iter(Itertools::intersperse(
+            [
+                (failed_count > 0).then(|| span!("{} failed", failed_count).red()),
+                (moved_count > 0).then(|| span!("{} moved", moved_count).yellow()),
+            ]
+            .into_iter()
+            .flatten(),
+            span!(" "),
+        ));
+        // How many of the feeds have resolved (e.g. "loading 3/12"), so that one slow feed holding up the
+        // rest can be told apart from all of them being slow
+        let progress_label = match self.feed.loading_progress() {
+            Some((fetched_count, total_count)) => {
+                span!("loading {}/{} ", fetched_count, total_count).dim()
+            }
+            None => span!(""),
+        };
+        let [title_area, _, throbber_area, _, progress_area, issues_area] = horizontal![
+            ==title_len,
+            ==1,
+            ==1,
+            ==1,
+            ==progress_label.width() as u16,
+            ==issues_label.width() as u16
+        ]
+        .areas(h_left_area);
+        frame.render_widget(progress_label, progress_area);
+        frame.render_widget(issues_label, issues_area);
 
         if self.feed.is_loading() {
             let tui_throbber = throbber_widgets_tui::Throbber::default()
@@ -187,7 +718,7 @@ impl App {
 
         frame.render_widget(
             line![
-                span!(app_name).magenta().bold(),
+                span!(app_name).fg(self.config.theme.highlight).bold(),
                 span!(" "),
                 span!(app_version).blue()
             ]
@@ -195,232 +726,2187 @@ impl App {
             title_area,
         );
 
+        // Nothing is shown until there are items (e.g. while the feeds are first loading)
+        let unread_label = match (self.feed.item_count(), self.feed.unread_count()) {
+            (0, _) => span!(""),
+            (_, 0) => span!("all read  ").dim(),
+            (_, unread_count) => span!("{} unread  ", unread_count).yellow(),
+        };
         frame.render_widget(
-            line!(chrono::Local::now().format(LONG_TIMESTAMP_FMT).to_string())
+            line![
+                span!(self.feed.sort_mode().indicator()).dim(),
+                span!("  "),
+                unread_label,
+                span!(
+                    chrono::Local::now()
+                        .format(&self.config.time_format)
+                        .to_string()
+                )
                 .cyan()
-                .right_aligned(),
+            ]
+            .right_aligned(),
             h_right_area,
         );
 
         self.feed.render(frame, main_area);
-
-        let help_key_desc = [
-            ("j/k/↑/↓", "scroll"),
-            ("g/G", "top/btm"),
-            ("Enter", "expand"),
-            ("o", "open"),
-            ("q", "close"),
-            ("Ctrl+D", "exit"),
-        ];
+        if self.show_key_help {
+            self.render_key_help(frame, main_area);
+        }
+        if self.confirm_quit {
+            Self::render_quit_prompt(frame, main_area);
+        }
 
         let mut help_spans = vec![];
-        for (i, (key, desc)) in help_key_desc.iter().enumerate() {
+        for (i, (key, desc)) in self.key_hints().iter().enumerate() {
             if i > 0 {
                 help_spans.push(span!(" | "));
             }
             help_spans.extend(vec![span!(key).bold(), span!(" {}", desc)]);
         }
-        frame.render_widget(
-            // Custom fixed colour to ensure readability (against dark themed terminals)
-            Line::from(help_spans).fg(Color::Rgb(100, 116, 139)),
-            footer_area,
-        );
+        // Transient status messages take the place of the key hints until they expire, and the search
+        // query takes the place of both while it is typed or filters the list. Selecting lines to copy takes
+        // the place of all of them, as keys do something else meanwhile
+        match (
+            self.feed.selected_line_count(),
+            self.feed.search_query(),
+            self.feed.status(),
+        ) {
+            (Some(line_count), _, _) => frame.render_widget(
+                line![
+                    span!("-- VISUAL --").fg(self.config.theme.highlight).bold(),
+                    span!(
+                        " {} line{} · y copy · Esc cancel",
+                        line_count,
+                        if line_count == 1 { "" } else { "s" }
+                    )
+                    .fg(self.config.theme.help)
+                ],
+                footer_area,
+            ),
+            (None, Some(query), _) => {
+                let cursor = if self.feed.is_searching() { "█" } else { "" };
+                frame.render_widget(
+                    line![
+                        span!("/").fg(self.config.theme.highlight).bold(),
+                        span!(query),
+                        span!(cursor).dim()
+                    ],
+                    footer_area,
+                )
+            }
+            (None, None, Some(status)) => frame.render_widget(line!(status).cyan(), footer_area),
+            (None, None, None) => frame.render_widget(
+                Line::from(help_spans).fg(self.config.theme.help),
+                footer_area,
+            ),
+        }
 
         if let Some(fps_widget) = &mut self.fps {
             fps_widget.render(fps_area, frame.buffer_mut());
         }
+
+        // Colors are stripped from the whole frame (rather than styled away widget by widget), so that
+        // nothing is left colored, while modifiers (e.g. bold, dim) are kept to tell elements apart
+        if self.config.no_color {
+            for cell in &mut frame.buffer_mut().content {
+                cell.set_fg(Color::Reset).set_bg(Color::Reset);
+            }
+        }
     }
 }
 
 struct FeedWidget {
     app_event_tx: Sender<AppEvent>,
 
+    config: Arc<Config>,
+    feed_urls: Vec<String>,
+    // Labels of the groups in the feeds file, in the order they are first listed, where none means that
+    // the list is not grouped
+    groups: Vec<String>,
+    collapsed_groups: HashSet<String>,
+    // Group label of every grouped feed, keyed by feed URL
+    feed_groups: Arc<HashMap<String, String>>,
+
     show_help: bool,
+    // Transient message shown in the footer (e.g. to confirm an action), shared with the background tasks
+    // that report their outcome, which notify `data_changed` to show it
+    status: Arc<Mutex<Option<(String, Instant)>>>,
 
     data: Arc<RwLock<FeedWidgetData>>,
+    // Notified whenever `data` is updated in the background
+    data_changed: Arc<Notify>,
+    // Notified whenever persisted state (e.g. read items) changes
+    state_changed: Arc<Notify>,
+    // Held while the state file is written
+    state_write_lock: Arc<AsyncMutex<()>>,
+    // Feeds of the current fetch that are yet to resolve, out of `loading_total`
     loading_count: Arc<AtomicUsize>,
+    loading_total: usize,
     http_client: Client,
+    // Client for enclosure downloads, whose requests are not bounded by `--timeout` as a whole
+    download_client: Client,
+    // Downloads of the images in the content of expanded items, or `None` if images are not shown
+    images: Option<ImageCache>,
+    // Targets of the permanent redirects (i.e. 301 and 308) followed by `http_client`, keyed by the
+    // redirected URL
+    permanent_redirects: Arc<Mutex<HashMap<String, String>>>,
 
     tb_state: TableState,
     tb_cum_row_heights: Vec<usize>, // Cumulative rendered height of each row in the table
+    tb_wrapped_titles: WrappedTitles,
+    tb_wrapped_pub_dates: WrappedPubDates,
+    // Height of the table as of the last render, i.e. a page when scrolling by pages
+    tb_viewport_height: u16,
+    // Area of the table as of the last render, to find the row under a mouse click
+    tb_area: Rect,
+    // Selected row (index, item id) as of the last render, to keep the selected item selected when items
+    // are merged or sorted above it. Unset for the first row during the initial load, where the first
+    // (newest) item stays selected
+    tb_selection_anchor: Option<(usize, NonZeroU64)>,
+    initial_load_done: bool,
     sb_state: ScrollbarState,
 
+    show_sidebar: bool,
+    // Show absolute instead of relative publish dates in the list
+    absolute_dates: bool,
+    show_urls: bool,
+    // Items hidden from the list while only unread items are shown, i.e. those read as of when the mode
+    // was turned on. Items read since stay in the list (e.g. the item being read), until the mode is
+    // turned on again. `None` when every item is shown
+    hidden_read_ids: Option<HashSet<NonZeroU64>>,
+    show_errors: bool,
+    // Query the list is filtered by (case-insensitively, on titles), empty for no filter
+    search_query: String,
+    // Whether keys are typed into `search_query`
+    is_searching: bool,
+
     exp_item: ExpandedItemWidget,
+    last_exp_content_area: Option<Rect>,
+    // Previously expanded items (id, scroll offset) for back navigation, most recent last
+    exp_item_history: Vec<(NonZeroU64, usize)>,
+    // Scroll offsets of closed items (along with when they were closed), restored when they are expanded
+    // again
+    exp_item_scroll_offsets: HashMap<NonZeroU64, (usize, Instant)>,
 }
 
 #[derive(Default)]
 struct FeedWidgetData {
     items: Vec<FeedItem>,
+    read_ids: HashSet<NonZeroU64>,
+    // URLs of the items in the bookmarks file
+    bookmarked_urls: HashSet<String>,
+    // Ids of items seen in previous sessions (i.e. as of launch)
+    seen_ids: HashSet<NonZeroU64>,
+    // When the items of previous sessions were last in the feed (as Unix timestamps), keyed by item id
+    last_seen: HashMap<NonZeroU64, i64>,
+    // Names of the successfully fetched feeds, keyed by feed URL
+    sources: HashMap<String, String>,
+    // Content (lines) of items transformed by external commands, keyed by item id and transform
+    transformed: HashMap<(NonZeroU64, ContentTransform), Vec<String>>,
+    pending_transforms: HashSet<(NonZeroU64, ContentTransform)>,
+    // Items whose out-of-line content is being fetched
+    pending_fetches: HashSet<NonZeroU64>,
+    sort_mode: SortMode,
+    // Feeds that failed to load (URL, error), shown in the error panel
+    failed_feeds: Vec<(String, String)>,
+    // Feeds that permanently moved (feed URL, new URL), whose URL in the feeds file should be updated
+    moved_feeds: Vec<(String, String)>,
 }
 
-enum Feed {
-    Atom(atom_syndication::Feed),
-    Rss(rss::Channel),
+#[derive(Clone, Copy, Default)]
+enum SortMode {
+    #[default]
+    Newest,
+    Oldest,
+    Title,
 }
 
-impl FeedWidget {
-    const HTTP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("RSSTERM_VERSION"),);
+impl SortMode {
+    fn next(self) -> Self {
+        match self {
+            SortMode::Newest => SortMode::Oldest,
+            SortMode::Oldest => SortMode::Title,
+            SortMode::Title => SortMode::Newest,
+        }
+    }
 
-    fn new(app_event_tx: Sender<AppEvent>) -> Self {
-        let http_client = Client::builder()
-            .user_agent(Self::HTTP_USER_AGENT)
-            .build()
-            .expect("Failed to create HTTP client");
-        Self {
-            app_event_tx,
-            http_client,
-            show_help: false,
-            data: Arc::new(RwLock::new(FeedWidgetData::default())),
-            loading_count: Arc::new(AtomicUsize::new(0)),
-            tb_state: TableState::default(),
-            tb_cum_row_heights: Vec::new(),
-            sb_state: ScrollbarState::default(),
-            exp_item: ExpandedItemWidget::default(),
+    fn indicator(&self) -> &'static str {
+        match self {
+            SortMode::Newest => "↓ newest",
+            SortMode::Oldest => "↑ oldest",
+            SortMode::Title => "A–Z",
         }
     }
+}
 
-    fn run(&mut self, chan_urls: Vec<String>) {
-        if chan_urls.is_empty() {
-            self.show_help = true;
-            return;
+// Content derived from an item's text by an external command (see `pipe_through_command`)
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum ContentTransform {
+    Translation,
+    Summary,
+}
+
+impl ContentTransform {
+    fn name(&self) -> &'static str {
+        match self {
+            ContentTransform::Translation => "translation",
+            ContentTransform::Summary => "summary",
         }
+    }
+}
 
-        let http_client = self.http_client.clone();
-        let data = Arc::clone(&self.data);
+// Transformed content available for the expanded item
+#[derive(Clone, Copy, Default)]
+struct TransformedContent<'a> {
+    translation: Option<&'a [String]>,
+    summary: Option<&'a [String]>,
+}
 
-        let loading_count = Arc::clone(&self.loading_count);
-        loading_count.store(chan_urls.len(), Ordering::SeqCst);
+impl FeedWidgetData {
+    // Forget that a feed failed or moved, as of a previous fetch of it
+    fn clear_feed_issues(&mut self, chan_url: &str) {
+        self.failed_feeds.retain(|(url, _)| url != chan_url);
+        self.moved_feeds.retain(|(url, _)| url != chan_url);
+    }
 
-        tokio::spawn(async move {
-            let mut query_set: JoinSet<Result<Feed, Box<dyn Error + Send + Sync>>> = JoinSet::new();
+    // Merge freshly parsed items into the feed, skipping items that are already present (by `id`) and
+    // keeping the feed sorted by `sort_mode`. Returns the items that were newly added and kept
+    //
+    // With a `per_source_limit` (or `total_limit`), only the newest items of each source (or of the whole
+    // feed) are kept, whatever the sort mode. The limits are enforced on the whole feed rather than just on
+    // the parsed items, so that items accumulated over several merges cannot exceed them either
+    fn merge(
+        &mut self,
+        parsed_items: Vec<FeedItem>,
+        per_source_limit: Option<usize>,
+        total_limit: Option<usize>,
+    ) -> Vec<FeedItem> {
+        let existing_ids: HashSet<NonZeroU64> = self.items.iter().map(|item| item.id).collect();
+        // The first occurrence of an item that is in several feeds is kept, i.e. the one already listed,
+        // or otherwise the one of the feed that resolved first
+        let mut seen_keys: HashSet<DuplicateKey> = self
+            .items
+            .iter()
+            .filter_map(FeedItem::duplicate_key)
+            .collect();
+        let new_items: Vec<FeedItem> = parsed_items
+            .into_iter()
+            .filter(|item| !existing_ids.contains(&item.id))
+            .filter(|item| item.duplicate_key().is_none_or(|key| seen_keys.insert(key)))
+            .collect();
 
-            for chan_url in chan_urls {
-                let local_http_client = http_client.clone();
-                query_set.spawn(async move {
-                    let http_resp = local_http_client.get(chan_url).send().await?;
-                    let http_resp_bytes = &http_resp.bytes().await?[..];
-                    match rss::Channel::read_from(http_resp_bytes) {
-                        Ok(rss_feed) => Ok(Feed::Rss(rss_feed)),
-                        Err(_) => match atom_syndication::Feed::read_from(http_resp_bytes) {
-                            Ok(atom_feed) => Ok(Feed::Atom(atom_feed)),
-                            Err(_) => Err(Box::from("Failed to parse feed")),
-                        },
-                    }
-                });
-            }
+        self.items.extend(new_items.iter().cloned());
+        self.sort();
 
-            while let Some(result) = query_set.join_next().await {
-                match result {
-                    Ok(Ok(parsed_feed)) => {
-                        let new_items: Vec<_> = match parsed_feed {
-                            Feed::Atom(atom_feed) => atom_feed
-                                .entries()
-                                .iter()
-                                .filter_map(FeedItem::from_atom_entry)
-                                .collect(),
-                            Feed::Rss(rss_feed) => rss_feed
-                                .items()
-                                .iter()
-                                .filter_map(FeedItem::from_rss_item)
-                                .collect(),
-                        };
-                        let mut data = data.write().unwrap();
-                        data.items.extend(new_items);
-                        data.items.sort_by(|a, b| b.pub_date.cmp(&a.pub_date));
-                    }
-                    Ok(Err(e)) => eprintln!("Feed fetch error: {}", e),
-                    Err(e) => eprintln!("Task failed: {}", e),
-                }
-                loading_count.fetch_sub(1, Ordering::SeqCst);
-            }
-        });
+        if per_source_limit.is_some() || total_limit.is_some() {
+            let mut newest_first: Vec<&FeedItem> = self.items.iter().collect();
+            newest_first.sort_by(|a, b| Self::cmp_newest(a, b));
+            let mut source_counts: HashMap<&str, usize> = HashMap::new();
+            let kept_ids: HashSet<NonZeroU64> = newest_first
+                .into_iter()
+                .filter(|item| {
+                    let count = source_counts.entry(item.source.as_str()).or_default();
+                    *count += 1;
+                    per_source_limit.is_none_or(|limit| *count <= limit)
+                })
+                .take(total_limit.unwrap_or(usize::MAX))
+                .map(|item| item.id)
+                .collect();
+            self.items.retain(|item| kept_ids.contains(&item.id));
+            return new_items
+                .into_iter()
+                .filter(|item| kept_ids.contains(&item.id))
+                .collect();
+        }
+
+        new_items
     }
 
-    fn is_loading(&self) -> bool {
-        self.loading_count.load(Ordering::SeqCst) > 0
+    fn cmp_newest(a: &FeedItem, b: &FeedItem) -> std::cmp::Ordering {
+        b.pub_date
+            .cmp(&a.pub_date)
+            .then_with(|| a.source.cmp(&b.source))
+            .then(a.feed_index.cmp(&b.feed_index))
     }
 
-    async fn handle_event(&mut self, event: AppEvent) {
-        let is_exp_item_active = self.exp_item.id.is_some();
-        match event {
-            AppEvent::Scroll(delta) => {
-                if is_exp_item_active {
-                    self.exp_item.scroll(delta);
-                } else {
-                    self.scroll_feed(delta);
-                }
-            }
-            AppEvent::Expand => {
-                let items = &self.data.read().unwrap().items;
-                if let Some(selected_item_i) = self.tb_state.selected() {
-                    if let Some(feed_item) = items.get(selected_item_i) {
-                        self.exp_item.id = Some(feed_item.id);
-                    }
-                }
-            }
-            AppEvent::Close => {
-                if self.exp_item.id.is_some() {
-                    self.exp_item = ExpandedItemWidget::default();
-                } else {
-                    // If the feed widget does not have a nested view that can be closed, we send a exit
-                    // event upstream. We can do this because if a widget receives an event, it is the
-                    // only active/focused widget of the entire app, as such the widget can safely
-                    // determine whether to exit the app
-                    self.app_event_tx.send(AppEvent::Exit).await.ok();
-                }
-            }
-            AppEvent::Open => self.open_selected(),
-            _ => (),
+    // Items with the same sort key are ordered as they appear in their feed, so that the order is
+    // deterministic across merges and sort mode changes (e.g. for feeds that give many items the same date)
+    fn sort(&mut self) {
+        match self.sort_mode {
+            SortMode::Newest => self.items.sort_by(Self::cmp_newest),
+            // Undated items are sorted last here too (as when sorting by newest)
+            SortMode::Oldest => self.items.sort_by(|a, b| {
+                (a.pub_date.is_none(), a.pub_date)
+                    .cmp(&(b.pub_date.is_none(), b.pub_date))
+                    .then_with(|| a.source.cmp(&b.source))
+                    .then(a.feed_index.cmp(&b.feed_index))
+            }),
+            // Untitled items are sorted last, and items with the same title from newest to oldest
+            SortMode::Title => self.items.sort_by_cached_key(|item| {
+                (
+                    item.title.is_none(),
+                    item.title.as_deref().map(str::to_lowercase),
+                    Reverse(item.pub_date),
+                    item.source.clone(),
+                    item.feed_index,
+                )
+            }),
         }
     }
 
-    fn scroll_feed(&mut self, delta: isize) {
-        match delta {
-            isize::MIN => self.tb_state.select_first(),
-            isize::MAX => self.tb_state.select_last(),
-            delta if delta < 0 => self.tb_state.scroll_up_by((-delta) as u16),
-            delta => self.tb_state.scroll_down_by(delta as u16),
+    // State to persist, where the items in the feed are recorded as last seen now, and items that have not
+    // been in the feed for long are pruned
+    fn state(&self) -> State {
+        let now = Utc::now().timestamp();
+        let mut state = State::default();
+        for id in &self.read_ids {
+            state.items.entry(*id).or_default().read = true;
         }
-        // NOTE: The range of selected_i is [0, data.len() - 1]
-        // This is likely to allow developers to catch overflow events to handle wrap arounds
-        // Currently, we are not allowing wrap arounds, hence we are clamping the value
-        let selected_item_i = self
-            .tb_state
-            .selected()
-            .unwrap_or(0)
-            .clamp(0, self.tb_cum_row_heights.len().saturating_sub(1));
-        // If the first item is selected, there should be no scrollbar movement (i.e. position 0)
-        self.sb_state = self.sb_state.position(
-            self.tb_cum_row_heights
-                .get(selected_item_i.saturating_sub(1))
-                .unwrap_or(&0)
-                * min(selected_item_i, 1),
-        );
+        for id in &self.seen_ids {
+            state.items.entry(*id).or_default().seen = true;
+        }
+        for (id, item_state) in &mut state.items {
+            item_state.last_seen = Some(self.last_seen.get(id).copied().unwrap_or(now));
+        }
+        for item in &self.items {
+            let item_state = state.items.entry(item.id).or_default();
+            item_state.seen = true;
+            item_state.last_seen = Some(now);
+        }
+        state.prune(now);
+        state
     }
 
-    fn open_selected(&self) {
-        let items = &self.data.read().unwrap().items;
-
-        let open_result = self
-            .tb_state
-            .selected()
-            .and_then(|i| items.get(i))
-            .and_then(|item| item.url.as_ref())
-            .map(|url| open::that(url));
-
-        match open_result {
-            Some(Err(e)) => eprintln!("Failed to open URL: {}", e),
-            None => eprintln!("No item selected or no URL available"),
-            _ => {}
+    // Whether an item is new, i.e. published within `new_within` if set (which undated items never are),
+    // otherwise unseen before launch
+    fn is_new(&self, item: &FeedItem, new_within: Option<TimeDelta>) -> bool {
+        match new_within {
+            Some(new_within) => item
+                .pub_date
+                .is_some_and(|pub_date| chrono::Local::now() - pub_date <= new_within),
+            None => !self.seen_ids.contains(&item.id),
         }
     }
+}
 
-    fn render(&mut self, frame: &mut Frame, area: Rect) {
-        if self.show_help {
-            let help_para = para_wrap!(text![
+// Spawns the user-provided new item hook without blocking the caller. The command's stdio is detached
+// from the terminal so that it cannot corrupt the TUI, and failures are reported as a status
+fn spawn_new_item_hook(
+    cmd: &str,
+    item: &FeedItem,
+    source: &str,
+    status: Arc<Mutex<Option<(String, Instant)>>>,
+    data_changed: Arc<Notify>,
+) {
+    let child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(cmd)
+        .env(
+            "RSSTERM_ITEM_TITLE",
+            item.title.as_deref().unwrap_or_default(),
+        )
+        .env("RSSTERM_ITEM_URL", item.url.as_deref().unwrap_or_default())
+        .env("RSSTERM_ITEM_SOURCE", source)
+        .env(
+            "RSSTERM_ITEM_DATE",
+            item.pub_date
+                .map(|pub_date| pub_date.to_rfc3339())
+                .unwrap_or_default(),
+        )
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+
+    match child {
+        Ok(mut child) => {
+            tokio::spawn(async move {
+                let message = match child.wait().await {
+                    Ok(exit_status) if !exit_status.success() => {
+                        format!("New item hook exited with {}", exit_status)
+                    }
+                    Err(e) => format!("New item hook failed: {}", e),
+                    _ => return,
+                };
+                report_status(&status, &data_changed, message);
+            });
+        }
+        Err(e) => {
+            let message = format!("Failed to spawn new item hook: {}", e);
+            report_status(&status, &data_changed, message);
+        }
+    }
+}
+
+// Spawns the user-provided open command (`--open-cmd`) for `url`, where `{url}` in the command is replaced
+// by the shell-quoted URL (or the URL is appended, without a `{url}`), so the template must not quote it
+// again. The command is detached from the terminal, and a failed exit is reported as a status
+fn spawn_open_cmd(
+    template: &str,
+    url: &str,
+    status: Arc<Mutex<Option<(String, Instant)>>>,
+    data_changed: Arc<Notify>,
+) -> io::Result<()> {
+    let url = shell_quote(url);
+    let cmd = match template.contains("{url}") {
+        true => template.replace("{url}", &url),
+        false => format!("{} {}", template, url),
+    };
+    let mut child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(cmd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    tokio::spawn(async move {
+        let message = match child.wait().await {
+            Ok(exit_status) if !exit_status.success() => {
+                format!("Open command exited with {}", exit_status)
+            }
+            Err(e) => format!("Open command failed: {}", e),
+            _ => return,
+        };
+        report_status(&status, &data_changed, message);
+    });
+    Ok(())
+}
+
+// Shows `message` in the footer from a background task, which can't borrow the widget
+fn report_status(
+    status: &Mutex<Option<(String, Instant)>>,
+    data_changed: &Notify,
+    message: String,
+) {
+    *status.lock().unwrap() = Some((message, Instant::now()));
+    data_changed.notify_one();
+}
+
+#[allow(clippy::large_enum_variant)]
+enum Feed {
+    Atom(atom_syndication::Feed),
+    Rss(rss::Channel),
+    Json(JsonFeed),
+}
+
+type FetchResult = Result<Feed, Box<dyn Error + Send + Sync>>;
+
+pub(crate) const HTTP_USER_AGENT: &str =
+    concat!(env!("CARGO_PKG_NAME"), "/", env!("RSSTERM_VERSION"));
+// Some servers content-negotiate and only serve the feed (rather than HTML) when feed types are explicitly
+// accepted
+pub(crate) const HTTP_ACCEPT: &str = "application/rss+xml, application/atom+xml, application/feed+json, application/xml;q=0.9, text/xml;q=0.9, application/json;q=0.9, */*;q=0.8";
+
+// Title of the feed a response body is, parsed the same way as fetched feeds are, so that `rssterm add`
+// accepts exactly the feeds that can be shown
+pub(crate) fn parse_feed_title(
+    body: &[u8],
+    url: &str,
+) -> Result<String, Box<dyn Error + Send + Sync>> {
+    Feed::parse(&gunzip_body(body)).map(|feed| feed.title(url))
+}
+
+impl Feed {
+    // Title of the feed, falling back to the host of the feed's URL if the feed has no title
+    fn title(&self, url: &str) -> String {
+        let title = match self {
+            Feed::Atom(atom_feed) => atom_feed.title.value.trim(),
+            Feed::Rss(rss_feed) => rss_feed.title().trim(),
+            Feed::Json(json_feed) => json_feed.title.trim(),
+        };
+        match title {
+            "" => Url::parse(url)
+                .ok()
+                .and_then(|url| url.host_str().map(str::to_owned))
+                .unwrap_or_else(|| url.to_owned()),
+            title => title.to_owned(),
+        }
+    }
+
+    // URL of the site the feed is of (i.e. the channel's `<link>` or Atom `alternate` link), if any
+    fn site_url(&self) -> Option<String> {
+        let site_url = match self {
+            Feed::Atom(atom_feed) => atom_feed
+                .links()
+                .iter()
+                .find(|link| link.rel == "alternate")
+                .map(|link| link.href.as_str()),
+            Feed::Rss(rss_feed) => Some(rss_feed.link()),
+            Feed::Json(json_feed) => json_feed.home_page_url.as_deref(),
+        };
+        site_url
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(str::to_owned)
+    }
+
+    // Delay before the first retry of a failed request, doubled for every subsequent retry
+    const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+
+    // Send a request, retrying failures that are likely transient (i.e. connection errors and server
+    // errors) up to `max_retries` times with exponential backoff. Client errors (4xx) are not retried
+    async fn send_with_retries(
+        http_req: RequestBuilder,
+        max_retries: u32,
+    ) -> reqwest::Result<Response> {
+        let mut retries = 0;
+        loop {
+            // Requests without a streamed body can always be cloned
+            let attempt = http_req
+                .try_clone()
+                .expect("request is cloneable")
+                .send()
+                .await;
+            let is_transient = match attempt {
+                Ok(ref http_resp) => http_resp.status().is_server_error(),
+                Err(ref e) => e.is_connect(),
+            };
+            if !is_transient || retries >= max_retries {
+                return attempt;
+            }
+            // The sleep only suspends this feed's task, so other feeds keep being fetched meanwhile
+            tokio::time::sleep(Self::RETRY_BASE_DELAY * 2u32.saturating_pow(retries)).await;
+            retries += 1;
+        }
+    }
+
+    // Fetch a feed with a conditional request if it was fetched before, reusing the cached feed if the
+    // server reports it as not modified
+    async fn fetch(
+        http_client: &Client,
+        http_cache: &HttpCache,
+        url: &str,
+        max_retries: u32,
+    ) -> FetchResult {
+        let cached = http_cache.get(url).await;
+
+        let mut http_req = http_client.get(url).header(ACCEPT, HTTP_ACCEPT);
+        if let Some(ref cached) = cached {
+            if let Some(ref etag) = cached.etag {
+                http_req = http_req.header(IF_NONE_MATCH, etag);
+            }
+            http_req = http_req.header(IF_MODIFIED_SINCE, cached.if_modified_since());
+        }
+        let http_resp = Self::send_with_retries(http_req, max_retries).await?;
+
+        if http_resp.status() == StatusCode::NOT_MODIFIED
+            && let Some(cached) = cached
+        {
+            return Self::parse(&cached.body);
+        }
+        let http_resp = http_resp.error_for_status()?;
+
+        let header = |name| {
+            http_resp
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned)
+        };
+        let (etag, last_modified) = (header(ETAG), header(LAST_MODIFIED));
+        let body = http_resp.bytes().await?;
+        let body = gunzip_body(&body);
+        let feed = Self::parse(&body)?;
+
+        // Only feeds that could be parsed are cached, so a broken response is never reused
+        if let Err(e) = http_cache.put(url, etag, last_modified, &body).await {
+            eprintln!("Failed to cache feed ({}): {}", url, e);
+        }
+        Ok(feed)
+    }
+
+    // Fetch a feed, and for paged Atom feeds (RFC 5005) follow `rel="next"` links up to `max_pages`
+    // pages in total, merging the entries of every page into the first page
+    async fn fetch_paged(
+        http_client: &Client,
+        http_cache: &HttpCache,
+        url: &str,
+        max_pages: usize,
+        max_retries: u32,
+    ) -> FetchResult {
+        let mut feed = Self::fetch(http_client, http_cache, url, max_retries).await?;
+        let Feed::Atom(ref mut atom_feed) = feed else {
+            return Ok(feed);
+        };
+
+        let mut visited_urls = HashSet::from([url.to_owned()]);
+        let mut next_url = Self::next_page_url(&atom_feed.links, &Url::parse(url)?);
+
+        while let Some(url) = next_url.take() {
+            if visited_urls.len() >= max_pages || !visited_urls.insert(url.to_string()) {
+                break;
+            }
+            // Failing to fetch a subsequent page should not discard the pages that were fetched
+            let Ok(Feed::Atom(next_page)) =
+                Self::fetch(http_client, http_cache, url.as_str(), max_retries).await
+            else {
+                break;
+            };
+            next_url = Self::next_page_url(&next_page.links, &url);
+            atom_feed.entries.extend(next_page.entries);
+        }
+
+        Ok(feed)
+    }
+
+    fn next_page_url(links: &[atom_syndication::Link], page_url: &Url) -> Option<Url> {
+        links
+            .iter()
+            .find(|link| link.rel == "next")
+            // `href` may be relative to the page it was found on
+            .and_then(|link| page_url.join(&link.href).ok())
+    }
+
+    // The content type of responses is not relied on, as servers often get it wrong (e.g. `text/html` for
+    // RSS), so every parser is tried and the body itself is sniffed to explain why none could parse it
+    fn parse(bytes: &[u8]) -> FetchResult {
+        let rss_error = match rss::Channel::read_from(bytes) {
+            Ok(rss_feed) => return Ok(Feed::Rss(rss_feed)),
+            Err(e) => e,
+        };
+        let atom_error = match atom_syndication::Feed::read_from(bytes) {
+            Ok(atom_feed) => return Ok(Feed::Atom(atom_feed)),
+            Err(e) => e,
+        };
+        if let Some(json_feed) = JsonFeed::read_from(bytes) {
+            return Ok(Feed::Json(json_feed));
+        }
+
+        if bytes.iter().all(u8::is_ascii_whitespace) {
+            return Err(Box::from("empty response, not a feed"));
+        }
+        let preview = body_preview(bytes);
+        // Either a webpage was added instead of its feed, or a misconfigured server responded with an HTML
+        // error page (with a 200 status)
+        let error = if is_html_document(bytes) {
+            format!(
+                "served HTML, not a feed (the URL of a webpage rather than of its feed?): {}",
+                preview
+            )
+        } else if is_xml_document(bytes) && String::from_utf8_lossy(bytes).contains("<feed") {
+            format!("malformed Atom feed ({}): {}", atom_error, preview)
+        } else if is_xml_document(bytes) {
+            format!("malformed RSS feed ({}): {}", rss_error, preview)
+        } else {
+            format!("not a feed: {}", preview)
+        };
+        Err(Box::from(error))
+    }
+}
+
+impl FeedWidget {
+    const MAX_EXP_ITEM_HISTORY: usize = 64;
+    // Closed items whose scroll offset is kept, beyond which the least recently closed are forgotten
+    const MAX_EXP_ITEM_SCROLL_OFFSETS: usize = 256;
+    const STATUS_DURATION: Duration = Duration::from_secs(3);
+    // Redirects followed per request, beyond which the request fails (e.g. redirect loops)
+    const MAX_REDIRECTS: usize = 5;
+    // Group of the feeds that are not listed under any group header
+    const UNCATEGORIZED_GROUP: &str = "Uncategorized";
+    const STATE_SAVE_DELAY: Duration = Duration::from_secs(2);
+
+    fn new(
+        app_event_tx: Sender<AppEvent>,
+        config: Arc<Config>,
+        image_picker: Option<Picker>,
+    ) -> Self {
+        let permanent_redirects: Arc<Mutex<HashMap<String, String>>> = Arc::default();
+        let redirect_policy = {
+            let permanent_redirects = Arc::clone(&permanent_redirects);
+            Policy::custom(move |attempt| {
+                if attempt.previous().len() > Self::MAX_REDIRECTS {
+                    return attempt.error("too many redirects");
+                }
+                if matches!(
+                    attempt.status(),
+                    StatusCode::MOVED_PERMANENTLY | StatusCode::PERMANENT_REDIRECT
+                ) && let Some(from_url) = attempt.previous().last()
+                {
+                    permanent_redirects
+                        .lock()
+                        .unwrap()
+                        .insert(from_url.to_string(), attempt.url().to_string());
+                }
+                attempt.follow()
+            })
+        };
+
+        let client_builder = |redirect_policy| {
+            let builder = Client::builder()
+                .user_agent(HTTP_USER_AGENT)
+                .redirect(redirect_policy);
+            // Proxies of the environment (e.g. HTTPS_PROXY) are used by default, and replaced by `--proxy`
+            match &config.proxy {
+                Some(proxy) => builder.proxy(
+                    Proxy::all(proxy.as_str())
+                        .expect("Proxy is validated when parsing arguments")
+                        .no_proxy(NoProxy::from_env()),
+                ),
+                None => builder,
+            }
+        };
+        let mut http_client = client_builder(redirect_policy);
+        let mut download_client = client_builder(Policy::limited(Self::MAX_REDIRECTS));
+        // Bounds every request (including reading the body), so that a hung server cannot keep the feeds
+        // loading forever. Enclosures (e.g. podcast episodes) can take much longer than that to download,
+        // so their reads are bounded instead, which only fails downloads that stall
+        if let Some(timeout) = config.timeout {
+            http_client = http_client.timeout(timeout);
+            download_client = download_client.read_timeout(timeout);
+        }
+        let http_client = http_client.build().expect("Failed to create HTTP client");
+        let download_client = download_client
+            .build()
+            .expect("Failed to create HTTP client");
+        let data_changed = Arc::new(Notify::new());
+        let images = image_picker
+            .map(|picker| ImageCache::new(picker, http_client.clone(), Arc::clone(&data_changed)));
+        Self {
+            app_event_tx,
+            show_sidebar: matches!(config.default_view, View::Overview),
+            absolute_dates: config.absolute_dates,
+            show_urls: !config.hide_urls,
+            hidden_read_ids: None,
+            show_errors: false,
+            search_query: String::new(),
+            is_searching: false,
+            config,
+            feed_urls: Vec::new(),
+            groups: Vec::new(),
+            collapsed_groups: HashSet::new(),
+            feed_groups: Arc::default(),
+            http_client,
+            download_client,
+            images,
+            show_help: false,
+            status: Arc::default(),
+            permanent_redirects,
+            data: Arc::new(RwLock::new(FeedWidgetData::default())),
+            data_changed,
+            state_changed: Arc::new(Notify::new()),
+            state_write_lock: Arc::default(),
+            loading_count: Arc::new(AtomicUsize::new(0)),
+            loading_total: 0,
+            tb_state: TableState::default(),
+            tb_cum_row_heights: Vec::new(),
+            tb_wrapped_titles: WrappedTitles::default(),
+            tb_wrapped_pub_dates: WrappedPubDates::default(),
+            tb_viewport_height: 0,
+            tb_area: Rect::default(),
+            tb_selection_anchor: None,
+            initial_load_done: false,
+            sb_state: ScrollbarState::default(),
+            exp_item: ExpandedItemWidget::default(),
+            last_exp_content_area: None,
+            exp_item_history: Vec::new(),
+            exp_item_scroll_offsets: HashMap::new(),
+        }
+    }
+
+    // Fetch the feeds of `chan_urls`, where `feed_groups` has the group label of every grouped feed
+    fn run(&mut self, chan_urls: Vec<String>, feed_groups: HashMap<String, String>) {
+        if chan_urls.is_empty() {
+            self.show_help = true;
+            return;
+        }
+        self.feed_urls = chan_urls.clone();
+        // Feeds listed before any group header are uncategorised, and thus come first
+        if !feed_groups.is_empty() {
+            self.groups = chan_urls
+                .iter()
+                .map(|url| {
+                    feed_groups
+                        .get(url)
+                        .map_or(Self::UNCATEGORIZED_GROUP, String::as_str)
+                })
+                .unique()
+                .map(str::to_owned)
+                .collect();
+        }
+        self.feed_groups = Arc::new(feed_groups);
+        self.fetch_feeds(false);
+    }
+
+    // Re-fetch every feed, merging the items that are new (by id) into the current items
+    fn refresh(&mut self) {
+        if self.feed_urls.is_empty() || self.is_loading() {
+            return;
+        }
+        self.exp_item_history.clear();
+        self.fetch_feeds(true);
+    }
+
+    // Fetch the feeds in the background, each merging its items into `data` as soon as it is fetched, where
+    // `is_refresh` is unset for the initial fetch at launch
+    fn fetch_feeds(&mut self, is_refresh: bool) {
+        let chan_urls = self.feed_urls.clone();
+        let feed_groups = Arc::clone(&self.feed_groups);
+        let http_client = self.http_client.clone();
+        let http_cache = HttpCache::new(self.config.http_cache_dir.clone());
+        let data = Arc::clone(&self.data);
+        let data_changed = Arc::clone(&self.data_changed);
+        let config = Arc::clone(&self.config);
+        let permanent_redirects = Arc::clone(&self.permanent_redirects);
+        let status = Arc::clone(&self.status);
+
+        self.loading_total = chan_urls.len();
+        let loading_count = Arc::clone(&self.loading_count);
+        loading_count.store(chan_urls.len(), Ordering::SeqCst);
+        data_changed.notify_one();
+
+        tokio::spawn(async move {
+            let mut query_set: JoinSet<(String, FetchResult)> = JoinSet::new();
+            // Every feed is spawned right away, but only fetched once it gets a permit, and results are
+            // still handled as each fetch completes
+            let fetch_permits = Arc::new(Semaphore::new(config.max_concurrency));
+
+            for chan_url in chan_urls {
+                let local_http_client = http_client.clone();
+                let local_http_cache = http_cache.clone();
+                let local_fetch_permits = Arc::clone(&fetch_permits);
+                let max_pages = if config.follow_paging {
+                    config.max_pages
+                } else {
+                    1
+                };
+                let max_retries = config.max_retries;
+                query_set.spawn(async move {
+                    let _permit = local_fetch_permits
+                        .acquire()
+                        .await
+                        .expect("Semaphore is never closed");
+                    let feed = Feed::fetch_paged(
+                        &local_http_client,
+                        &local_http_cache,
+                        &chan_url,
+                        max_pages,
+                        max_retries,
+                    )
+                    .await;
+                    (chan_url, feed)
+                });
+            }
+
+            while let Some(result) = query_set.join_next().await {
+                match result {
+                    Ok((chan_url, Ok(parsed_feed))) => {
+                        let source = parsed_feed.title(&chan_url);
+                        let site_url = parsed_feed.site_url();
+                        let mut parsed_items: Vec<_> = match parsed_feed {
+                            Feed::Atom(atom_feed) => atom_feed
+                                .entries()
+                                .iter()
+                                .filter_map(|entry| FeedItem::from_atom_entry(entry, &source))
+                                .collect(),
+                            Feed::Rss(rss_feed) => rss_feed
+                                .items()
+                                .iter()
+                                .filter_map(|item| FeedItem::from_rss_item(item, &source))
+                                .collect(),
+                            Feed::Json(json_feed) => json_feed
+                                .items
+                                .iter()
+                                .filter_map(|item| FeedItem::from_json_item(item, &source))
+                                .collect(),
+                        };
+                        for (feed_index, item) in parsed_items.iter_mut().enumerate() {
+                            item.feed_index = feed_index;
+                            item.group = feed_groups.get(&chan_url).cloned();
+                            item.source_url = site_url.clone();
+                            item.update_content_links();
+                        }
+                        let moved_url = Self::moved_url(&permanent_redirects, &chan_url);
+                        let mut data = data.write().unwrap();
+                        data.clear_feed_issues(&chan_url);
+                        if let Some(moved_url) = moved_url {
+                            data.moved_feeds.push((chan_url.clone(), moved_url));
+                        }
+                        data.sources.insert(chan_url.clone(), source);
+                        let new_items = data.merge(
+                            parsed_items,
+                            config.max_items_per_feed,
+                            config.max_items_total,
+                        );
+                        // Only items that arrive with a refresh are new to the hook, as every item is new to
+                        // the (initially empty) list at launch. Items seen in previous sessions are not new
+                        // either, e.g. when they reappear after having been dropped by `--max-items`
+                        if let Some(cmd) = &config.on_new_item_cmd
+                            && is_refresh
+                        {
+                            new_items
+                                .iter()
+                                .filter(|item| !data.seen_ids.contains(&item.id))
+                                .for_each(|item| {
+                                    spawn_new_item_hook(
+                                        cmd,
+                                        item,
+                                        &chan_url,
+                                        Arc::clone(&status),
+                                        Arc::clone(&data_changed),
+                                    )
+                                });
+                        }
+                    }
+                    Ok((chan_url, Err(e))) => {
+                        let http_error = e.downcast_ref::<reqwest::Error>();
+                        let is_timeout = http_error.is_some_and(reqwest::Error::is_timeout);
+                        let is_redirect = http_error.is_some_and(reqwest::Error::is_redirect);
+                        // Failures to connect to (or authenticate with) the proxy look like failures of
+                        // the feed itself, unless the proxy is mentioned
+                        let is_proxy_error = http_error.is_some_and(|e| {
+                            e.is_connect()
+                                || e.status() == Some(StatusCode::PROXY_AUTHENTICATION_REQUIRED)
+                        });
+                        let proxy = Self::proxy_for(&config, &chan_url).filter(|_| is_proxy_error);
+                        let status = http_error.and_then(reqwest::Error::status);
+                        let error = match config.timeout {
+                            Some(timeout) if is_timeout => {
+                                format!("timed out after {}s", timeout.as_secs())
+                            }
+                            _ if is_redirect => format!(
+                                "redirected more than {} times (e.g. a redirect loop)",
+                                Self::MAX_REDIRECTS
+                            ),
+                            _ => match status {
+                                Some(status) => Self::status_error(status, config.max_retries),
+                                None => e.to_string(),
+                            },
+                        };
+                        let error = match proxy {
+                            Some(proxy) => format!("{} (via proxy {})", error, proxy),
+                            None => error,
+                        };
+                        let mut data = data.write().unwrap();
+                        data.clear_feed_issues(&chan_url);
+                        data.failed_feeds.push((chan_url, error));
+                    }
+                    Err(e) => eprintln!("Task failed: {}", e),
+                }
+                loading_count.fetch_sub(1, Ordering::SeqCst);
+                data_changed.notify_one();
+            }
+        });
+    }
+
+    // Error for a feed whose server responded with an error status, e.g. "HTTP 404 Not Found (client
+    // error)". Server errors are only reported once retrying them (see `send_with_retries`) gave up
+    fn status_error(status: StatusCode, max_retries: u32) -> String {
+        let kind = match status {
+            _ if status.is_client_error() => "client error".to_string(),
+            _ if status.is_server_error() && max_retries > 0 => format!(
+                "server error, after {} {}",
+                max_retries,
+                if max_retries == 1 { "retry" } else { "retries" }
+            ),
+            _ if status.is_server_error() => "server error".to_string(),
+            _ => "unexpected status".to_string(),
+        };
+        format!("HTTP {} ({})", status, kind)
+    }
+
+    // Proxy (without its credentials) that requests to `url` go through, i.e. `--proxy` or otherwise that
+    // of the environment for the URL's scheme, unless the host is excluded by NO_PROXY. This follows how
+    // `http_client` picks proxies, so that failures can mention the proxy they went through
+    fn proxy_for(config: &Config, url: &str) -> Option<String> {
+        let url = Url::parse(url).ok()?;
+        let host = url.host_str()?;
+        let env_var = |name: &str| {
+            env::var(name)
+                .or_else(|_| env::var(name.to_lowercase()))
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+        };
+        let no_proxy = env_var("NO_PROXY").unwrap_or_default();
+        let is_excluded = no_proxy
+            .split(',')
+            .map(|entry| entry.trim().trim_start_matches('.'))
+            .filter(|entry| !entry.is_empty())
+            .any(|entry| entry == "*" || host == entry || host.ends_with(&format!(".{}", entry)));
+        if is_excluded {
+            return None;
+        }
+
+        let proxy = config.proxy.clone().or_else(|| {
+            match url.scheme() {
+                "https" => env_var("HTTPS_PROXY"),
+                _ => env_var("HTTP_PROXY"),
+            }
+            .or_else(|| env_var("ALL_PROXY"))
+        })?;
+        match Url::parse(&proxy) {
+            Ok(mut proxy_url) if !proxy_url.username().is_empty() => {
+                proxy_url.set_username("").ok();
+                proxy_url.set_password(None).ok();
+                Some(proxy_url.to_string())
+            }
+            _ => Some(proxy),
+        }
+    }
+
+    // Where a feed permanently moved to, i.e. the URL at the end of the permanent redirects from `url`
+    // (a later temporary redirect, such as through a tracker, is not a move)
+    fn moved_url(
+        permanent_redirects: &Mutex<HashMap<String, String>>,
+        url: &str,
+    ) -> Option<String> {
+        let permanent_redirects = permanent_redirects.lock().unwrap();
+        let mut moved_url = None;
+        for _ in 0..Self::MAX_REDIRECTS {
+            let Some(to_url) = permanent_redirects.get(moved_url.as_deref().unwrap_or(url)) else {
+                break;
+            };
+            moved_url = Some(to_url.to_owned());
+        }
+        moved_url.filter(|moved_url| moved_url != url)
+    }
+
+    async fn fetch_text(&self, url: &str) -> reqwest::Result<String> {
+        self.http_client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await
+    }
+
+    fn redraw_signal(&self) -> Arc<Notify> {
+        Arc::clone(&self.data_changed)
+    }
+
+    fn item_count(&self) -> usize {
+        self.data.read().unwrap().items.len()
+    }
+
+    fn unread_count(&self) -> usize {
+        let data = self.data.read().unwrap();
+        data.items
+            .iter()
+            .filter(|item| !data.read_ids.contains(&item.id))
+            .count()
+    }
+
+    fn failed_count(&self) -> usize {
+        self.data.read().unwrap().failed_feeds.len()
+    }
+
+    fn moved_count(&self) -> usize {
+        self.data.read().unwrap().moved_feeds.len()
+    }
+
+    fn is_loading(&self) -> bool {
+        self.loading_count.load(Ordering::SeqCst) > 0
+    }
+
+    // Feeds of the current fetch that resolved so far, out of all of them. `None` once all have resolved
+    fn loading_progress(&self) -> Option<(usize, usize)> {
+        let loading_count = self.loading_count.load(Ordering::SeqCst);
+        (loading_count > 0).then(|| {
+            let total_count = self.loading_total.max(loading_count);
+            (total_count - loading_count, total_count)
+        })
+    }
+
+    async fn handle_event(&mut self, event: AppEvent) {
+        let is_exp_item_active = self.exp_item.id.is_some();
+        match event {
+            AppEvent::Scroll(delta) => {
+                if is_exp_item_active {
+                    self.exp_item.scroll(delta);
+                } else {
+                    self.scroll_feed(delta);
+                }
+            }
+            AppEvent::ScrollPage(halves) => {
+                if is_exp_item_active {
+                    self.exp_item.scroll_page(halves);
+                } else {
+                    self.scroll_feed_page(halves);
+                }
+            }
+            AppEvent::Select(row_i) if !is_exp_item_active => self.select_row(row_i),
+            AppEvent::Expand if self.selected_group_header().is_some() => {
+                self.toggle_selected_group()
+            }
+            AppEvent::Expand if self.selected_is_link_only() => self.open_selected(),
+            AppEvent::Expand => {
+                let mut data = self.data.write().unwrap();
+                if let Some(item_id) = self.selected_item(&data.items).map(|item| item.id) {
+                    self.exp_item.id = Some(item_id);
+                    self.exp_item.scroll_offset = self
+                        .exp_item_scroll_offsets
+                        .get(&item_id)
+                        .map_or(0, |(scroll_offset, _)| *scroll_offset);
+                    self.mark_read(&mut data, item_id);
+                    self.exp_item.prewrap(
+                        &data.items,
+                        self.last_exp_content_area,
+                        &self.config,
+                        self.images.as_ref(),
+                    );
+                }
+            }
+            AppEvent::ToggleLineSelection if is_exp_item_active => self.exp_item.toggle_selection(),
+            // Closing while selecting lines only ends the selection
+            AppEvent::Close if self.exp_item.selection.is_some() => self.exp_item.selection = None,
+            AppEvent::Close => {
+                if let Some(item_id) = self.exp_item.id {
+                    if self.exp_item_history.last().map(|(id, _)| *id) != Some(item_id) {
+                        self.exp_item_history
+                            .push((item_id, self.exp_item.scroll_offset));
+                    }
+                    if self.exp_item_history.len() > Self::MAX_EXP_ITEM_HISTORY {
+                        self.exp_item_history.remove(0);
+                    }
+                    self.remember_scroll_offset(item_id, self.exp_item.scroll_offset);
+                    self.exp_item = ExpandedItemWidget::default();
+                } else if self.search_query().is_some() {
+                    self.clear_search();
+                } else {
+                    // If the feed widget does not have a nested view that can be closed, we ask upstream
+                    // to confirm quitting. We can do this because if a widget receives an event, it is
+                    // the only active/focused widget of the entire app, as such the widget can safely
+                    // determine that there is nothing left to close
+                    self.app_event_tx.send(AppEvent::ConfirmQuit).await.ok();
+                }
+            }
+            AppEvent::Back => self.expand_previous(),
+            AppEvent::MarkAllRead => self.mark_all_read(),
+            AppEvent::ToggleRead => self.toggle_selected_read(),
+            AppEvent::Bookmark => self.toggle_selected_bookmark(),
+            AppEvent::Refresh => self.refresh(),
+            AppEvent::ToggleSidebar => self.toggle_sidebar(),
+            AppEvent::ToggleGroup if !is_exp_item_active => self.toggle_selected_group(),
+            AppEvent::ToggleDates => self.absolute_dates = !self.absolute_dates,
+            AppEvent::ToggleUrls => self.show_urls = !self.show_urls,
+            AppEvent::ToggleUnreadOnly => self.toggle_unread_only(),
+            AppEvent::ToggleErrors => self.show_errors = !self.show_errors,
+            AppEvent::CycleSort if !is_exp_item_active => self.cycle_sort_mode(),
+            AppEvent::Search if !is_exp_item_active => self.is_searching = true,
+            AppEvent::SearchInput(c) => {
+                self.search_query.push(c);
+                self.on_search_query_changed();
+            }
+            AppEvent::SearchDelete => {
+                self.search_query.pop();
+                self.on_search_query_changed();
+            }
+            AppEvent::SearchSubmit => self.is_searching = false,
+            AppEvent::SearchClear if !is_exp_item_active => self.clear_search(),
+            AppEvent::ExportOpml => self.export_opml(),
+            AppEvent::Open => self.open_selected(),
+            AppEvent::OpenSite => self.open_selected_site(),
+            AppEvent::OpenEnclosure => self.open_selected_enclosure(),
+            AppEvent::SendItem => self.send_selected(),
+            AppEvent::DownloadEnclosure => self.download_selected_enclosure(),
+            AppEvent::OpenLink(number) if is_exp_item_active => self.open_expanded_link(number),
+            AppEvent::Translate if is_exp_item_active => {
+                self.transform_expanded(ContentTransform::Translation)
+            }
+            AppEvent::Summarize if is_exp_item_active => {
+                self.transform_expanded(ContentTransform::Summary)
+            }
+            AppEvent::FetchContent if is_exp_item_active => self.fetch_expanded_content(),
+            _ => (),
+        }
+    }
+
+    fn scroll_feed(&mut self, delta: isize) {
+        // Rows of the list as of the last render, where the selection is clamped to the last row
+        let last_i = self.tb_cum_row_heights.len().saturating_sub(1);
+        let selected_i = self.tb_state.selected().map(|i| i.min(last_i));
+        let wraps = self.config.wrap_scroll && !self.tb_cum_row_heights.is_empty();
+        match delta {
+            isize::MIN => self.tb_state.select_first(),
+            isize::MAX => self.tb_state.select_last(),
+            // Wrapping only happens once at the end, so that a page scroll stops at the last (or first)
+            // item rather than overshooting into the other end of the list
+            delta if wraps && delta < 0 && selected_i == Some(0) => self.tb_state.select_last(),
+            delta if wraps && delta > 0 && selected_i == Some(last_i) => {
+                self.tb_state.select_first()
+            }
+            delta if delta < 0 => self.tb_state.scroll_up_by((-delta) as u16),
+            delta => self.tb_state.scroll_down_by(delta as u16),
+        }
+        self.sb_state = self.sb_state.position(Self::scrollbar_position(
+            &self.tb_state,
+            &self.tb_cum_row_heights,
+        ));
+    }
+
+    // Scroll the list by `halves` half pages of the list's height, i.e. to the row at about that height
+    // above (or below) the selected row, where rows (e.g. those with URLs) may be taller than a line
+    fn scroll_feed_page(&mut self, halves: isize) {
+        let Some(last_i) = self.tb_cum_row_heights.len().checked_sub(1) else {
+            return;
+        };
+        let selected_i = self.tb_state.selected().unwrap_or(0).min(last_i);
+        let row_top = |i: usize| i.checked_sub(1).map_or(0, |i| self.tb_cum_row_heights[i]);
+        let page_height = self.tb_viewport_height as usize * halves.unsigned_abs() / 2;
+        let target_i = match halves > 0 {
+            true => {
+                let target_top = row_top(selected_i) + page_height;
+                (selected_i + 1..=last_i)
+                    .find(|&i| row_top(i) >= target_top)
+                    .unwrap_or(last_i)
+            }
+            false => {
+                let target_top = row_top(selected_i).saturating_sub(page_height);
+                (0..selected_i)
+                    .rev()
+                    .find(|&i| row_top(i) <= target_top)
+                    .unwrap_or(0)
+            }
+        };
+        // At the end of the list, the scroll is left to `scroll_feed` (i.e. to clamp or wrap around)
+        let delta = match target_i as isize - selected_i as isize {
+            0 => halves.signum(),
+            delta => delta,
+        };
+        self.scroll_feed(delta);
+    }
+
+    fn select_row(&mut self, row_i: usize) {
+        self.tb_state.select(Some(row_i));
+        self.sb_state = self.sb_state.position(Self::scrollbar_position(
+            &self.tb_state,
+            &self.tb_cum_row_heights,
+        ));
+    }
+
+    // Row of the list at `position` (e.g. of a mouse click) as of the last render, where the bottom margin
+    // of a row is part of it. `None` outside of the rows, and while an item is expanded (i.e. the list is
+    // not shown)
+    fn row_at(&self, position: Position) -> Option<usize> {
+        if self.exp_item.id.is_some() || !self.tb_area.contains(position) {
+            return None;
+        }
+        let data = self.data.read().unwrap();
+        let row_count = self.visible_rows(&data.items).len();
+        drop(data);
+        // Rows scrolled past are above the table, i.e. the row is found by its height from the first row
+        let offset = self.tb_state.offset();
+        let scrolled_height = offset
+            .checked_sub(1)
+            .and_then(|i| self.tb_cum_row_heights.get(i))
+            .copied()
+            .unwrap_or(0);
+        let y = scrolled_height + (position.y - self.tb_area.y) as usize;
+        let row_i = self
+            .tb_cum_row_heights
+            .partition_point(|&height| height <= y);
+        (row_i < row_count.min(self.tb_cum_row_heights.len())).then_some(row_i)
+    }
+
+    // Rows (from `start` up to `end`) that fit in a table of `height` rows scrolled to `offset`, scrolled
+    // further if needed for `selected` to be visible. This is how the table itself picks the rows to show,
+    // but based on the heights of the rows only, so that only the visible rows need to be drawn
+    fn row_bounds(
+        tb_cum_row_heights: &[usize],
+        selected: Option<usize>,
+        offset: usize,
+        height: u16,
+    ) -> (usize, usize) {
+        let row_count = tb_cum_row_heights.len();
+        let height_with_margin = |i: usize| {
+            tb_cum_row_heights[i] - i.checked_sub(1).map_or(0, |i| tb_cum_row_heights[i])
+        };
+        // Every row but the last has a (one row) bottom margin
+        let row_height = |i: usize| height_with_margin(i) - (i + 1 < row_count) as usize;
+        let max_height = height as usize;
+
+        let offset = offset.min(row_count.saturating_sub(1));
+        let (mut start, mut end, mut rows_height) = (offset, offset, 0);
+        while end < row_count && rows_height + row_height(end) <= max_height {
+            rows_height += height_with_margin(end);
+            end += 1;
+        }
+        let Some(selected) = selected.filter(|_| row_count > 0) else {
+            return (start, end);
+        };
+        // The selected row is kept even if it is taller than the table, which then shows its top
+        let selected = selected.min(row_count - 1);
+        while selected >= end {
+            rows_height += height_with_margin(end);
+            end += 1;
+            while rows_height > max_height && start < selected {
+                rows_height -= height_with_margin(start);
+                start += 1;
+            }
+        }
+        while selected < start {
+            start -= 1;
+            rows_height += height_with_margin(start);
+            while rows_height > max_height && end > selected + 1 {
+                end -= 1;
+                rows_height -= height_with_margin(end);
+            }
+        }
+        (start, end)
+    }
+
+    // Scrollbar position of the selected row, i.e. the height of the rows above it
+    fn scrollbar_position(tb_state: &TableState, tb_cum_row_heights: &[usize]) -> usize {
+        // NOTE: The range of selected_i is [0, data.len() - 1]
+        // This is likely to allow developers to catch overflow events to handle wrap arounds
+        // Wrap arounds (with `--wrap-scroll`) are handled by `scroll_feed`, hence we are clamping the value
+        let selected_item_i = tb_state
+            .selected()
+            .unwrap_or(0)
+            .clamp(0, tb_cum_row_heights.len().saturating_sub(1));
+        // If the first item is selected, there should be no scrollbar movement (i.e. position 0)
+        tb_cum_row_heights
+            .get(selected_item_i.saturating_sub(1))
+            .unwrap_or(&0)
+            * min(selected_item_i, 1)
+    }
+
+    fn remember_scroll_offset(&mut self, item_id: NonZeroU64, scroll_offset: usize) {
+        self.exp_item_scroll_offsets
+            .insert(item_id, (scroll_offset, Instant::now()));
+        if self.exp_item_scroll_offsets.len() > Self::MAX_EXP_ITEM_SCROLL_OFFSETS
+            && let Some(oldest_id) = (self.exp_item_scroll_offsets.iter())
+                .min_by_key(|(_, (_, closed_at))| *closed_at)
+                .map(|(id, _)| *id)
+        {
+            self.exp_item_scroll_offsets.remove(&oldest_id);
+        }
+    }
+
+    // Re-expand the most recently closed item (i.e. other than the currently expanded one) at the scroll
+    // position it was left at, similar to a browser's back navigation
+    fn expand_previous(&mut self) {
+        while let Some((item_id, scroll_offset)) = self.exp_item_history.pop() {
+            if self.exp_item.id == Some(item_id) {
+                continue;
+            }
+            let data = self.data.read().unwrap();
+            if data.items.iter().any(|item| item.id == item_id) {
+                self.exp_item = ExpandedItemWidget {
+                    id: Some(item_id),
+                    scroll_offset,
+                    ..Default::default()
+                };
+                self.exp_item.prewrap(
+                    &data.items,
+                    self.last_exp_content_area,
+                    &self.config,
+                    self.images.as_ref(),
+                );
+                return;
+            }
+        }
+    }
+
+    // Toggle a transform (e.g. translation) of the expanded item's content, requesting it from the
+    // configured command if the item has not been transformed yet
+    fn transform_expanded(&mut self, transform: ContentTransform) {
+        let (cmd, show) = match transform {
+            ContentTransform::Translation => (
+                self.config.translate_cmd.clone(),
+                &mut self.exp_item.show_translation,
+            ),
+            ContentTransform::Summary => (
+                self.config.summarize_cmd.clone(),
+                &mut self.exp_item.show_summary,
+            ),
+        };
+        let Some(cmd) = cmd else {
+            return self.set_status(format!("No {} command configured", transform.name()));
+        };
+        let Some(item_id) = self.exp_item.id else {
+            return;
+        };
+
+        *show = !*show;
+
+        let key = (item_id, transform);
+        let mut data = self.data.write().unwrap();
+        if !*show || data.transformed.contains_key(&key) || data.pending_transforms.contains(&key) {
+            return;
+        }
+        let Some(text) = data
+            .items
+            .iter()
+            .find(|item| item.id == item_id)
+            .and_then(|item| item.content.as_ref().or(item.description.as_ref()))
+            .map(|lines| lines.iter().map(|l| without_image(l)).join("\n"))
+        else {
+            return;
+        };
+        data.pending_transforms.insert(key);
+
+        let data = Arc::clone(&self.data);
+        let data_changed = Arc::clone(&self.data_changed);
+        let status = Arc::clone(&self.status);
+        tokio::spawn(async move {
+            let output = pipe_through_command(&cmd, text).await;
+            let mut data = data.write().unwrap();
+            data.pending_transforms.remove(&key);
+            match output {
+                Ok(output) => {
+                    data.transformed
+                        .insert(key, output.lines().map(str::to_owned).collect());
+                    data_changed.notify_one();
+                }
+                Err(e) => {
+                    drop(data);
+                    let message = format!("Failed to get {}: {}", transform.name(), e);
+                    report_status(&status, &data_changed, message);
+                }
+            }
+        });
+    }
+
+    // Fetch the out-of-line content (Atom `<content src="...">`) of the expanded item in the background,
+    // replacing the item's content once fetched
+    fn fetch_expanded_content(&mut self) {
+        let Some(item_id) = self.exp_item.id else {
+            return;
+        };
+        let mut data = self.data.write().unwrap();
+        let Some(content_src) = data
+            .items
+            .iter()
+            .find(|item| item.id == item_id)
+            .and_then(|item| item.content_src.clone())
+        else {
+            return;
+        };
+        if !data.pending_fetches.insert(item_id) {
+            return;
+        }
+        drop(data);
+        self.set_status("Fetching content…".to_string());
+
+        let http_client = self.http_client.clone();
+        let data = Arc::clone(&self.data);
+        let data_changed = Arc::clone(&self.data_changed);
+        let status = Arc::clone(&self.status);
+        tokio::spawn(async move {
+            let fetched = async {
+                let response = http_client
+                    .get(&content_src)
+                    .send()
+                    .await?
+                    .error_for_status()?;
+                let content_type = response
+                    .headers()
+                    .get(CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_owned);
+                let text = response.text().await?;
+                reqwest::Result::Ok(parse_atom_content(&text, content_type.as_deref()))
+            }
+            .await;
+
+            let mut data = data.write().unwrap();
+            data.pending_fetches.remove(&item_id);
+            match fetched {
+                Ok(content) => {
+                    if let Some(item) = data.items.iter_mut().find(|item| item.id == item_id) {
+                        item.content = Some(content);
+                        item.content_src = None;
+                        item.update_content_links();
+                    }
+                    data_changed.notify_one();
+                }
+                Err(e) => {
+                    drop(data);
+                    let message = format!("Failed to fetch content ({}): {}", content_src, e);
+                    report_status(&status, &data_changed, message);
+                }
+            }
+        });
+    }
+
+    fn selected_is_link_only(&self) -> bool {
+        let data = self.data.read().unwrap();
+        self.selected_item(&data.items)
+            .is_some_and(FeedItem::is_link_only)
+    }
+
+    // Items match by title, or by category for queries starting with `#` (e.g. "#rust")
+    fn matches_search(&self, item: &FeedItem) -> bool {
+        let query = self.search_query.to_lowercase();
+        if let Some(category_query) = query.strip_prefix('#') {
+            return item
+                .categories
+                .iter()
+                .any(|category| category.to_lowercase().contains(category_query));
+        }
+        query.is_empty()
+            || item
+                .title
+                .as_ref()
+                .is_some_and(|title| title.to_lowercase().contains(&query))
+    }
+
+    // Items shown in the list, i.e. those matching the search query (and unread, when only unread items
+    // are shown)
+    fn visible_items<'a>(&self, items: &'a [FeedItem]) -> Vec<&'a FeedItem> {
+        items
+            .iter()
+            .filter(|item| self.matches_search(item))
+            .filter(|item| {
+                self.hidden_read_ids
+                    .as_ref()
+                    .is_none_or(|hidden_read_ids| !hidden_read_ids.contains(&item.id))
+            })
+            .collect()
+    }
+
+    // Show only the unread items (or every item again), keeping the selected item selected if it is still
+    // shown and otherwise selecting the row closest to it
+    fn toggle_unread_only(&mut self) {
+        let data = self.data.read().unwrap();
+        let selected_id = self.selected_item(&data.items).map(|item| item.id);
+        self.hidden_read_ids = match self.hidden_read_ids {
+            Some(_) => None,
+            None => Some(data.read_ids.clone()),
+        };
+
+        let row_count = self.visible_rows(&data.items).len();
+        let selected_i = selected_id
+            .and_then(|item_id| self.row_index(&data.items, item_id))
+            .or_else(|| {
+                self.tb_state
+                    .selected()
+                    .map(|i| i.min(row_count.saturating_sub(1)))
+            })
+            .filter(|_| row_count > 0);
+        drop(data);
+        self.tb_state.select(selected_i);
+        self.sb_state = self.sb_state.position(Self::scrollbar_position(
+            &self.tb_state,
+            &self.tb_cum_row_heights,
+        ));
+        self.set_status(match self.hidden_read_ids {
+            Some(_) => "Showing unread items only".to_string(),
+            None => "Showing all items".to_string(),
+        });
+    }
+
+    fn group_of(item: &FeedItem) -> &str {
+        item.group.as_deref().unwrap_or(Self::UNCATEGORIZED_GROUP)
+    }
+
+    fn has_groups(&self) -> bool {
+        !self.groups.is_empty()
+    }
+
+    // Rows of the list, i.e. the visible items, under a header per group (in the order of the feeds file)
+    // when the list is grouped. Items keep their order (i.e. the sort mode) within their group
+    fn visible_rows<'a>(&self, items: &'a [FeedItem]) -> Vec<ListRow<'a>> {
+        let mut items = self.visible_items(items);
+        if self.groups.is_empty() {
+            return items.into_iter().map(ListRow::Item).collect();
+        }
+
+        items.sort_by_key(|item| {
+            let group = Self::group_of(item);
+            self.groups.iter().position(|label| label == group)
+        });
+        let mut rows = Vec::new();
+        for (label, group_items) in &items.into_iter().chunk_by(|item| Self::group_of(item)) {
+            let group_items: Vec<_> = group_items.collect();
+            let collapsed = self.collapsed_groups.contains(label);
+            rows.push(ListRow::Group {
+                label,
+                item_count: group_items.len(),
+                collapsed,
+            });
+            if !collapsed {
+                rows.extend(group_items.into_iter().map(ListRow::Item));
+            }
+        }
+        rows
+    }
+
+    // Index of the row of an item in the list, if the item is shown
+    fn row_index(&self, items: &[FeedItem], item_id: NonZeroU64) -> Option<usize> {
+        self.visible_rows(items)
+            .iter()
+            .position(|row| row.item().is_some_and(|item| item.id == item_id))
+    }
+
+    // Selected item of the list, where the selected row may also be a group header (i.e. no item)
+    fn selected_item<'a>(&self, items: &'a [FeedItem]) -> Option<&'a FeedItem> {
+        let selected_i = self.tb_state.selected()?;
+        self.visible_rows(items).get(selected_i)?.item()
+    }
+
+    // Label of the selected group header, if a group header is selected
+    fn selected_group_header(&self) -> Option<String> {
+        let data = self.data.read().unwrap();
+        let selected_i = self.tb_state.selected()?;
+        match self.visible_rows(&data.items).get(selected_i)? {
+            ListRow::Group { label, .. } => Some(label.to_string()),
+            ListRow::Item(_) => None,
+        }
+    }
+
+    // Collapse (or expand) the group of the selected row, selecting the group's header as any item of a
+    // collapsed group is hidden
+    fn toggle_selected_group(&mut self) {
+        if self.groups.is_empty() {
+            return;
+        }
+        let label = match self.selected_group_header() {
+            Some(label) => label,
+            None => {
+                let data = self.data.read().unwrap();
+                let Some(item) = self.selected_item(&data.items) else {
+                    return;
+                };
+                Self::group_of(item).to_owned()
+            }
+        };
+        if !self.collapsed_groups.remove(&label) {
+            self.collapsed_groups.insert(label.clone());
+        }
+
+        let data = self.data.read().unwrap();
+        let header_i = self.visible_rows(&data.items).iter().position(
+            |row| matches!(row, ListRow::Group { label: row_label, .. } if *row_label == label),
+        );
+        drop(data);
+        self.tb_state.select(header_i);
+    }
+
+    fn is_searching(&self) -> bool {
+        self.is_searching
+    }
+
+    // The query filtering the list, or `None` when the list is not filtered nor a query is being typed
+    fn search_query(&self) -> Option<&str> {
+        (self.is_searching || !self.search_query.is_empty()).then_some(self.search_query.as_str())
+    }
+
+    // Matches are shown from the top, as the previously selected row may now be any (or no) item
+    fn on_search_query_changed(&mut self) {
+        self.tb_state = TableState::default();
+        self.sb_state = self.sb_state.position(0);
+    }
+
+    // Clear the search query, keeping the selected match selected in the full list
+    fn clear_search(&mut self) {
+        if self.search_query().is_none() {
+            return;
+        }
+        let data = self.data.read().unwrap();
+        let selected_id = self.selected_item(&data.items).map(|item| item.id);
+        drop(data);
+
+        self.search_query.clear();
+        self.is_searching = false;
+        self.tb_state = TableState::default();
+        if let Some(selected_id) = selected_id {
+            let data = self.data.read().unwrap();
+            let selected_i = self.row_index(&data.items, selected_id);
+            drop(data);
+            self.tb_state.select(selected_i);
+        }
+    }
+
+    // Open a link of the expanded item's content by its footnote number, where numbers without a footnote
+    // are ignored
+    fn open_expanded_link(&self, number: usize) {
+        let data = self.data.read().unwrap();
+        let Some(url) = data
+            .items
+            .iter()
+            .find(|item| Some(item.id) == self.exp_item.id)
+            .and_then(|item| item.content_links.get(number.wrapping_sub(1)))
+        else {
+            return;
+        };
+        if let Err(e) = self.open_url(url) {
+            self.set_status(format!("Failed to open URL: {}", e));
+        }
+    }
+
+    // `url` as it is opened or copied, i.e. without tracking parameters (see `--strip-param`)
+    fn clean_url(&self, url: &str) -> String {
+        strip_query_params(url, &self.config.strip_params)
+    }
+
+    // Open `url` with the open command (`--open-cmd`), or otherwise in the default application
+    fn open_url(&self, url: &str) -> io::Result<()> {
+        let url = self.clean_url(url);
+        match &self.config.open_cmd {
+            Some(open_cmd) => spawn_open_cmd(
+                open_cmd,
+                &url,
+                self.status.clone(),
+                self.data_changed.clone(),
+            ),
+            None => open::that(url),
+        }
+    }
+
+    // Number of lines selected in the expanded item, or `None` when no lines are being selected
+    fn selected_line_count(&self) -> Option<usize> {
+        self.exp_item.selected_lines().map(|lines| lines.count())
+    }
+
+    // Text of the lines selected in the expanded item, ending the selection
+    fn take_selected_lines(&mut self) -> Option<Vec<String>> {
+        let content = self.exp_item.cached_render_content.as_ref()?;
+        let lines = (self.exp_item.selected_lines()?)
+            .map(|i| {
+                let spans = content[i].spans.iter();
+                spans.map(|span| span.content.as_ref()).collect()
+            })
+            .collect();
+        self.exp_item.selection = None;
+        Some(lines)
+    }
+
+    fn selected_url(&self) -> Option<String> {
+        let data = self.data.read().unwrap();
+        let url = self.selected_item(&data.items)?.url.as_ref()?;
+        Some(self.clean_url(url))
+    }
+
+    fn open_selected(&self) {
+        let mut data = self.data.write().unwrap();
+
+        let selected_item = self.selected_item(&data.items);
+        let selected_id = selected_item.map(|item| item.id);
+        let open_result = selected_item
+            .and_then(|item| item.url.as_ref())
+            .map(|url| self.open_url(url));
+
+        match open_result {
+            Some(Err(e)) => self.set_status(format!("Failed to open URL: {}", e)),
+            None => self.set_status("No item selected or no URL available".to_string()),
+            Some(Ok(_)) => {
+                if let Some(item_id) = selected_id {
+                    self.mark_read(&mut data, item_id);
+                }
+            }
+        }
+    }
+
+    // Open the site of the selected item's feed, or the item itself if its feed has no site. Unlike opening
+    // the item, this does not mark the item as read
+    fn open_selected_site(&self) {
+        let data = self.data.read().unwrap();
+        let open_result = self
+            .selected_item(&data.items)
+            .and_then(|item| item.source_url.as_ref().or(item.url.as_ref()))
+            .map(|url| self.open_url(url));
+
+        match open_result {
+            Some(Err(e)) => self.set_status(format!("Failed to open URL: {}", e)),
+            None => self.set_status("No item selected or no URL available".to_string()),
+            Some(Ok(_)) => (),
+        }
+    }
+
+    // Pipe the URL of the selected (or expanded) item, or its content with `--send-content`, to the send
+    // command in the background, where the outcome is reported as a status
+    fn send_selected(&mut self) {
+        let Some(cmd) = self.config.send_cmd.clone() else {
+            return self.set_status("No send command configured (see --send-cmd)".to_string());
+        };
+        let data = self.data.read().unwrap();
+        let Some(item) = self.selected_item(&data.items) else {
+            return;
+        };
+        let input = match self.config.send_content {
+            true => (item.content.as_ref().or(item.description.as_ref()))
+                .map(|lines| lines.iter().map(|l| without_image(l)).join("\n")),
+            false => item.url.as_ref().map(|url| self.clean_url(url)),
+        };
+        drop(data);
+        let Some(input) = input else {
+            return self.set_status("Nothing to send (the item has no URL or content)".to_string());
+        };
+        self.set_status("Sending item…".to_string());
+
+        let status = self.status.clone();
+        let data_changed = self.data_changed.clone();
+        tokio::spawn(async move {
+            let message = match pipe_through_command(&cmd, input).await {
+                Ok(_) => "Sent item".to_string(),
+                Err(e) => format!("Failed to send item: {}", e),
+            };
+            report_status(&status, &data_changed, message);
+        });
+    }
+
+    // Open (e.g. play) the enclosure of the selected item, marking the item as read
+    fn open_selected_enclosure(&mut self) {
+        let mut data = self.data.write().unwrap();
+        let Some(item) = self.selected_item(&data.items) else {
+            return;
+        };
+        let item_id = item.id;
+        let Some(enclosure_url) = item
+            .enclosure
+            .as_ref()
+            .map(|enclosure| enclosure.url.clone())
+        else {
+            drop(data);
+            return self.set_status("No enclosure to open".to_string());
+        };
+        match open::that(&enclosure_url) {
+            Ok(_) => self.mark_read(&mut data, item_id),
+            Err(e) => {
+                drop(data);
+                self.set_status(format!("Failed to open enclosure: {}", e));
+            }
+        }
+    }
+
+    // Download the enclosure of the selected item in the background, unless it is being (or was already)
+    // downloaded
+    fn download_selected_enclosure(&mut self) {
+        let mut data = self.data.write().unwrap();
+        let Some(item_id) = self.selected_item(&data.items).map(|item| item.id) else {
+            return;
+        };
+        let Some(item) = data.items.iter_mut().find(|item| item.id == item_id) else {
+            return;
+        };
+        let Some(enclosure_url) = item
+            .enclosure
+            .as_ref()
+            .map(|enclosure| enclosure.url.clone())
+        else {
+            drop(data);
+            return self.set_status("No enclosure to download".to_string());
+        };
+        match &item.enclosure_download {
+            Some(EnclosureDownload::InProgress) => return,
+            Some(EnclosureDownload::Done(path)) => {
+                let status = format!("Already downloaded to {}", path.display());
+                drop(data);
+                return self.set_status(status);
+            }
+            Some(EnclosureDownload::Failed) | None => (),
+        }
+        item.enclosure_download = Some(EnclosureDownload::InProgress);
+        drop(data);
+        self.set_status("Downloading enclosure…".to_string());
+
+        let download_client = self.download_client.clone();
+        let downloads_dir = self.config.downloads_dir.clone();
+        let data = Arc::clone(&self.data);
+        let data_changed = Arc::clone(&self.data_changed);
+        let status = Arc::clone(&self.status);
+        tokio::spawn(async move {
+            let downloaded =
+                download_to_dir(&download_client, &enclosure_url, &downloads_dir).await;
+            let (download, message) = match downloaded {
+                Ok(path) => {
+                    let message = format!("Downloaded to {}", path.display());
+                    (EnclosureDownload::Done(path), message)
+                }
+                Err(e) => (
+                    EnclosureDownload::Failed,
+                    format!("Failed to download enclosure: {}", e),
+                ),
+            };
+            if let Some(item) =
+                (data.write().unwrap().items.iter_mut()).find(|item| item.id == item_id)
+            {
+                item.enclosure_download = Some(download);
+            }
+            report_status(&status, &data_changed, message);
+        });
+    }
+
+    fn set_status(&self, message: String) {
+        *self.status.lock().unwrap() = Some((message, Instant::now()));
+    }
+
+    fn status(&self) -> Option<String> {
+        (self.status.lock().unwrap().as_ref())
+            .filter(|(_, instant)| instant.elapsed() < Self::STATUS_DURATION)
+            .map(|(message, _)| message.clone())
+    }
+
+    // Export the configured feeds as OPML next to the feeds file
+    fn export_opml(&mut self) {
+        let opml = {
+            let sources = &self.data.read().unwrap().sources;
+            let feeds: Vec<_> = self
+                .feed_urls
+                .iter()
+                .map(|url| OpmlFeed {
+                    title: sources.get(url).map_or(url.as_str(), String::as_str),
+                    url,
+                })
+                .collect();
+            opml::export(&feeds)
+        };
+
+        let export_file = self.config.feeds_file.with_extension("opml");
+        match std::fs::write(&export_file, opml) {
+            Ok(_) => self.set_status(format!("Exported feeds to {}", export_file.display())),
+            Err(e) => self.set_status(format!("Failed to export feeds: {}", e)),
+        }
+    }
+
+    // Cycle through the sort modes, keeping the selected item selected
+    fn cycle_sort_mode(&mut self) {
+        let mut data = self.data.write().unwrap();
+        let selected_id = self.selected_item(&data.items).map(|item| item.id);
+
+        data.sort_mode = data.sort_mode.next();
+        data.sort();
+
+        if let Some(selected_id) = selected_id {
+            let selected_i = self.row_index(&data.items, selected_id);
+            self.tb_state.select(selected_i);
+        }
+    }
+
+    fn sort_mode(&self) -> SortMode {
+        self.data.read().unwrap().sort_mode
+    }
+
+    fn toggle_sidebar(&mut self) {
+        self.show_sidebar = !self.show_sidebar;
+    }
+
+    fn load_state(&mut self, state: State) {
+        let mut data = self.data.write().unwrap();
+        data.read_ids = state.ids_where(|item_state| item_state.read).collect();
+        data.seen_ids = state.ids_where(|item_state| item_state.seen).collect();
+        // Items of state files written before the time was recorded count as last seen now
+        let now = Utc::now().timestamp();
+        data.last_seen = (state.items.iter())
+            .map(|(id, item_state)| (*id, item_state.last_seen.unwrap_or(now)))
+            .collect();
+    }
+
+    // Write the state to `state_file` without blocking the runtime. Writes are serialised (as the
+    // background writer and the save on exit would otherwise write the same temporary file at once), and
+    // the state is only taken once it is this write's turn, so that an earlier state never overwrites a
+    // later one
+    async fn save_state(
+        data: &RwLock<FeedWidgetData>,
+        state_file: &Path,
+        state_write_lock: &AsyncMutex<()>,
+    ) -> io::Result<()> {
+        let _guard = state_write_lock.lock().await;
+        let state = data.read().unwrap().state();
+        let state_file = state_file.to_owned();
+        tokio::task::spawn_blocking(move || state.save(&state_file)).await?
+    }
+
+    // Persist the state in the background whenever it changes. Changes made within `STATE_SAVE_DELAY`
+    // of each other are batched into a single write
+    fn spawn_state_writer(&self) {
+        let data = Arc::clone(&self.data);
+        let state_changed = Arc::clone(&self.state_changed);
+        let state_file = self.config.state_file.clone();
+        let state_write_lock = Arc::clone(&self.state_write_lock);
+        tokio::spawn(async move {
+            loop {
+                state_changed.notified().await;
+                tokio::time::sleep(Self::STATE_SAVE_DELAY).await;
+                if let Err(e) = Self::save_state(&data, &state_file, &state_write_lock).await {
+                    eprintln!("Failed to save state: {}", e);
+                }
+            }
+        });
+    }
+
+    fn mark_read(&self, data: &mut FeedWidgetData, item_id: NonZeroU64) {
+        if data.read_ids.insert(item_id) {
+            self.state_changed.notify_one();
+        }
+    }
+
+    // Mark the items shown in the list as read, leaving those filtered out by the search or in collapsed
+    // groups as they are
+    fn mark_all_read(&mut self) {
+        let mut data = self.data.write().unwrap();
+        let unread_ids: Vec<_> = self
+            .visible_rows(&data.items)
+            .iter()
+            .filter_map(ListRow::item)
+            .map(|item| item.id)
+            .filter(|id| !data.read_ids.contains(id))
+            .collect();
+        if unread_ids.is_empty() {
+            return;
+        }
+        let count = unread_ids.len();
+        data.read_ids.extend(unread_ids);
+        drop(data);
+        self.state_changed.notify_one();
+        self.set_status(format!(
+            "Marked {} item{} read",
+            count,
+            if count == 1 { "" } else { "s" }
+        ));
+    }
+
+    fn toggle_selected_read(&self) {
+        let mut data = self.data.write().unwrap();
+        let Some(item_id) = self.selected_item(&data.items).map(|item| item.id) else {
+            return;
+        };
+        if !data.read_ids.remove(&item_id) {
+            data.read_ids.insert(item_id);
+        }
+        self.state_changed.notify_one();
+    }
+
+    // A bookmarks file that cannot be read shows no items as bookmarked, whereas bookmarking an item fails
+    // (rather than overwriting the file)
+    fn load_bookmarks(&mut self) {
+        match read_bookmarks(&self.config.bookmarks_file) {
+            Ok(bookmarks) => {
+                self.data.write().unwrap().bookmarked_urls =
+                    bookmarks.into_iter().map(|bookmark| bookmark.url).collect()
+            }
+            Err(e) => eprintln!("Failed to read bookmarks: {}", e),
+        }
+    }
+
+    // Bookmark the selected (or expanded) item, or remove its bookmark if it is bookmarked already
+    fn toggle_selected_bookmark(&mut self) {
+        let mut data = self.data.write().unwrap();
+        let Some(item) = self.selected_item(&data.items) else {
+            return;
+        };
+        let Some(url) = item.url.clone() else {
+            drop(data);
+            self.set_status("Cannot bookmark an item without a URL".to_string());
+            return;
+        };
+        let title = item.title.clone().unwrap_or_default();
+        let status = match toggle_bookmark(&self.config.bookmarks_file, &url, &title) {
+            Ok(true) => {
+                data.bookmarked_urls.insert(url);
+                "Bookmarked item".to_string()
+            }
+            Ok(false) => {
+                data.bookmarked_urls.remove(&url);
+                "Removed bookmark".to_string()
+            }
+            Err(e) => format!("Failed to update bookmarks: {}", e),
+        };
+        drop(data);
+        self.set_status(status);
+    }
+
+    // Sidebar overview of every source with its unread count (i.e. "source (n)")
+    fn render_sidebar(&self, frame: &mut Frame, area: Rect, data: &FeedWidgetData) {
+        let mut unread_counts: BTreeMap<&str, usize> = BTreeMap::new();
+        for item in &data.items {
+            *unread_counts.entry(item.source.as_str()).or_default() +=
+                (!data.read_ids.contains(&item.id)) as usize;
+        }
+        let total_unread: usize = unread_counts.values().sum();
+
+        let count_w = total_unread.to_string().len() as u16 + 2; // +2 for parentheses
+        let title_color = self.config.theme.title;
+        let sidebar_row = |name: String, unread: usize| {
+            let count_cell = line!(format!("({})", unread)).right_aligned();
+            match unread {
+                0 => row![line!(name), count_cell].dim(),
+                _ => row![line!(name).fg(title_color), count_cell.yellow()],
+            }
+        };
+
+        let sidebar_rows = chain(
+            [sidebar_row("All".to_string(), total_unread).bold()],
+            unread_counts
+                .iter()
+                .map(|(source, unread)| sidebar_row(source.to_string(), *unread)),
+        );
+
+        frame.render_widget(
+            Table::new(sidebar_rows, constraints![*=1, ==count_w]).block(
+                Block::default()
+                    .borders(Borders::RIGHT)
+                    .border_style(Color::DarkGray)
+                    .padding(Padding::right(1)),
+            ),
+            area,
+        );
+    }
+
+    // Panel listing the feeds that failed to load, followed by those that moved, overlaid on the bottom of
+    // `area`
+    fn render_errors(&self, frame: &mut Frame, area: Rect, data: &FeedWidgetData) {
+        let title = [
+            (data.failed_feeds.len(), "failed"),
+            (data.moved_feeds.len(), "moved"),
+        ]
+        .iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{} {}", count, label))
+        .join(", ");
+        let block = Block::bordered()
+            .border_type(BorderType::Rounded)
+            .border_style(Color::Red)
+            .title(line!(format!(" Feeds: {} ", title)).red().bold())
+            .padding(Padding::horizontal(1));
+        let inner_w = block.inner(area).width as usize;
+
+        let moved_notices = data.moved_feeds.iter().map(|(url, moved_url)| {
+            let notice = format!("moved permanently to {}, update the feeds file", moved_url);
+            (url, notice)
+        });
+        let error_lines: Vec<Line> = data
+            .failed_feeds
+            .iter()
+            .map(|(url, error)| (url, error.to_owned()))
+            .chain(moved_notices)
+            .flat_map(|(url, message)| {
+                chain!(
+                    wrap_then_apply(url, inner_w, |l| line!(l).bold())
+                        .into_iter()
+                        .map(|l| l.fg(self.config.theme.title)),
+                    wrap_then_apply(&message, inner_w, |l| line!(l).dim())
+                )
+            })
+            .collect();
+
+        // +2 for the borders
+        let panel_h = min(error_lines.len() as u16 + 2, max(area.height / 2, 3));
+        let [_, panel_area] = vertical![*=1, ==panel_h].areas(area);
+
+        frame.render_widget(Clear, panel_area);
+        frame.render_widget(Paragraph::new(error_lines).block(block), panel_area);
+    }
+
+    fn render(&mut self, frame: &mut Frame, area: Rect) {
+        // Hyperlinks are only (re)collected when the expanded item is rendered
+        self.exp_item.hyperlinks.clear();
+        self.render_view(frame, area);
+
+        let data = self.data.read().unwrap();
+        if self.show_errors && !(data.failed_feeds.is_empty() && data.moved_feeds.is_empty()) {
+            self.render_errors(frame, area, &data);
+            // The panel is drawn over the hyperlinks, which would otherwise be drawn over it in turn
+            drop(data);
+            self.exp_item.hyperlinks.clear();
+        }
+    }
+
+    // Links drawn in the last render (area, URL), to be made OSC 8 hyperlinks (see `App::draw_frame`)
+    fn hyperlinks(&self) -> &[(Rect, String)] {
+        &self.exp_item.hyperlinks
+    }
+
+    // Render the active view, i.e. the help, the expanded item, or the list of items
+    fn render_view(&mut self, frame: &mut Frame, area: Rect) {
+        if self.show_help {
+            let help_para = para_wrap!(text![
                 line!["NO FEEDS FOUND"].bold(),
                 line!(),
-                line!["Add RSS/Atom URLs to the feeds file to get started"].fg(WARM_WHITE_RGB),
+                line!["Add RSS/Atom URLs to the feeds file to get started"]
+                    .fg(self.config.theme.content),
                 line!(),
                 line![
                     span!("$ ").dim(),
@@ -436,17 +2922,84 @@ impl FeedWidget {
             return frame.render_widget(help_para, area);
         }
 
-        let feed_items = &self.data.read().unwrap().items;
+        let data = self.data.read().unwrap();
+        let feed_items = &data.items;
+
+        // Fill the otherwise blank area during a cold start, until the first items arrive
+        if feed_items.is_empty()
+            && let Some((fetched_count, total_count)) = self.loading_progress()
+        {
+            let fetching_para = para_wrap!(text![
+                line!(format!(
+                    "Fetching feeds… ({}/{})",
+                    fetched_count, total_count
+                ))
+                .dim()
+            ])
+            .block(Block::default().padding(Padding {
+                top: area.height / 3,
+                ..Padding::ZERO
+            }))
+            .centered();
+
+            return frame.render_widget(fetching_para, area);
+        }
 
         if let Some(exp_feed_item) = self
             .exp_item
             .id
             .and_then(|id| feed_items.iter().find(|item| item.id == id))
         {
-            return self.exp_item.render(frame, area, exp_feed_item);
+            let transformed_content = |transform| {
+                data.transformed
+                    .get(&(exp_feed_item.id, transform))
+                    .map(Vec::as_slice)
+            };
+            let transformed = TransformedContent {
+                translation: transformed_content(ContentTransform::Translation),
+                summary: transformed_content(ContentTransform::Summary),
+            };
+            self.exp_item.render(
+                frame,
+                area,
+                exp_feed_item,
+                transformed,
+                &self.config,
+                self.images.as_ref(),
+            );
+            self.last_exp_content_area = self.exp_item.content_area();
+            return;
+        }
+
+        let area = match self.show_sidebar {
+            true => {
+                let sidebar_w = (area.width / 4).clamp(16, 32);
+                let [sidebar_area, _, feed_area] = horizontal![==sidebar_w, ==1, *=1].areas(area);
+                self.render_sidebar(frame, sidebar_area, &data);
+                feed_area
+            }
+            false => area,
+        };
+
+        let rows = self.visible_rows(feed_items);
+        let no_match_text = match (self.search_query.is_empty(), &self.hidden_read_ids) {
+            (false, _) => Some("No items match the search"),
+            (true, Some(_)) => Some("No unread items (press U to show all items)"),
+            (true, None) => None,
+        };
+        if rows.is_empty()
+            && let Some(no_match_text) = no_match_text
+        {
+            let no_match_para = para_wrap!(text![line!(no_match_text).dim()])
+                .block(Block::default().padding(Padding {
+                    top: area.height / 3,
+                    ..Padding::ZERO
+                }))
+                .centered();
+            return frame.render_widget(no_match_para, area);
         }
 
-        let [tb_area, sb_area] = horizontal![*=1, ==2].areas(area);
+        let [mut tb_area, sb_area] = horizontal![*=1, ==2].areas(area);
 
         let tb_col_spacing = 2;
         let tb_col_layout = constraints![*=0, ==20%];
@@ -454,50 +3007,209 @@ impl FeedWidget {
         let tb_hl_symbol = ">> ";
         let tb_hl_symbol_len = tb_hl_symbol.len() as u16;
 
-        // Dynamically calculate the rendered width of each table column, required for text wrapping
-        let tb_col_areas: [Rect; 2] = Layout::horizontal(tb_col_layout)
-            .spacing(tb_col_spacing)
-            .areas(Rect {
-                x: tb_area.x + tb_hl_symbol_len,
-                width: tb_area.width.saturating_sub(tb_hl_symbol_len),
-                ..tb_area
-            });
+        let row_options = RowOptions {
+            absolute_date: self.absolute_dates,
+            show_url: self.show_urls,
+            source_tag: matches!(self.config.show_source, Some(SourceLabels::Tags)),
+            min_wrap_width: self.config.min_wrap_width,
+            theme: self.config.theme,
+        };
+        self.tb_wrapped_titles.invalidate_if_resized(area.width);
+        self.tb_wrapped_pub_dates.invalidate_if_stale();
+        let row_state = |i: usize, feed_item: &FeedItem| {
+            let is_read = data.read_ids.contains(&feed_item.id);
+            // Only unread items are badged as new, as a read item needs no further attention
+            let is_new = !is_read && data.is_new(feed_item, self.config.new_within);
+            let is_unseen = !data.seen_ids.contains(&feed_item.id);
+            let is_bookmarked = feed_item
+                .url
+                .as_ref()
+                .is_some_and(|url| data.bookmarked_urls.contains(url));
+            // Consecutive items from the same source share a single source label, shown on the first
+            // item of the run
+            let is_run_start = i == 0
+                || rows[i - 1]
+                    .item()
+                    .is_none_or(|prev_item| prev_item.source != feed_item.source);
+            let show_source_label =
+                matches!(self.config.show_source, Some(SourceLabels::Runs)) && is_run_start;
+            RowState {
+                is_read,
+                is_new,
+                is_unseen,
+                is_bookmarked,
+                show_source_label,
+            }
+        };
 
-        self.tb_cum_row_heights.resize(feed_items.len(), 0);
+        // Only the heights of the rows are computed for every row (for scrolling and the scrollbar), as
+        // building the rows themselves is only needed for those that are visible
+        let layout_rows = |tb_area: Rect,
+                           tb_cum_row_heights: &mut Vec<usize>,
+                           tb_wrapped_titles: &mut WrappedTitles,
+                           tb_wrapped_pub_dates: &mut WrappedPubDates| {
+            // Dynamically calculate the rendered width of each table column, required for text wrapping
+            let tb_col_areas: [Rect; 2] = Layout::horizontal(tb_col_layout)
+                .spacing(tb_col_spacing)
+                .areas(Rect {
+                    x: tb_area.x + tb_hl_symbol_len,
+                    width: tb_area.width.saturating_sub(tb_hl_symbol_len),
+                    ..tb_area
+                });
 
-        let mut tbl_total_content_height = 0;
-        let tb_rows: Vec<Row> = feed_items
-            .iter()
-            .enumerate()
-            .map(|(i, feed_item)| {
-                let (tb_row, tb_row_h) = feed_item.draw_row(&tb_col_areas);
+            tb_cum_row_heights.resize(rows.len(), 0);
 
-                let tb_row_btm_margin = (!(i == feed_items.len().saturating_sub(1))) as u16;
-                let tb_row_total_h = tb_row_h + tb_row_btm_margin;
-                tbl_total_content_height += tb_row_total_h as usize;
+            let mut tbl_total_content_height = 0;
+            for (i, row) in rows.iter().enumerate() {
+                let tb_row_h = match *row {
+                    ListRow::Group { .. } => GROUP_ROW_HEIGHT,
+                    ListRow::Item(feed_item) => {
+                        let title_lines = feed_item.title.as_ref().map(|title| {
+                            tb_wrapped_titles.get_or_wrap(
+                                feed_item.id,
+                                title,
+                                tb_col_areas[0].width as usize,
+                                row_options.min_wrap_width,
+                            )
+                        });
+                        let pub_date_lines = tb_wrapped_pub_dates.get_or_wrap(
+                            feed_item,
+                            tb_col_areas[1].width as usize,
+                            row_options,
+                        );
+                        feed_item.row_height(
+                            tb_col_areas[0].width as usize,
+                            &row_state(i, feed_item),
+                            title_lines,
+                            pub_date_lines,
+                            row_options,
+                        )
+                    }
+                };
+
+                let tb_row_btm_margin = (i != rows.len().saturating_sub(1)) as u16;
+                tbl_total_content_height += (tb_row_h + tb_row_btm_margin) as usize;
 
                 // Each row has a dynamic height determined by text wrapping. Therefore, cumulative row
                 // heights are updated every render cycle
-                self.tb_cum_row_heights[i] = tbl_total_content_height;
-                tb_row.bottom_margin(tb_row_btm_margin)
-            })
-            .collect();
+                tb_cum_row_heights[i] = tbl_total_content_height;
+            }
+            (tb_col_areas, tbl_total_content_height)
+        };
+
+        let (mut tb_col_areas, mut tbl_total_content_height) = layout_rows(
+            tb_area,
+            &mut self.tb_cum_row_heights,
+            &mut self.tb_wrapped_titles,
+            &mut self.tb_wrapped_pub_dates,
+        );
+
+        // Reclaim the scrollbar gutter when every row fits. Widening the table can only make rows shorter,
+        // so the rows are guaranteed to still fit
+        let show_scrollbar =
+            !self.config.auto_hide_scrollbar || tbl_total_content_height > area.height as usize;
+        if !show_scrollbar {
+            tb_area = area;
+            (tb_col_areas, tbl_total_content_height) = layout_rows(
+                tb_area,
+                &mut self.tb_cum_row_heights,
+                &mut self.tb_wrapped_titles,
+                &mut self.tb_wrapped_pub_dates,
+            );
+        }
 
         self.sb_state = self.sb_state.content_length(tbl_total_content_height);
 
-        // Select the expanded item if available, otherwise select first item if none selected
+        let row_of = |item_id| {
+            rows.iter()
+                .position(|row| row.item().is_some_and(|item| item.id == item_id))
+        };
+        // The anchored item is only followed if the selection was not moved since the last render
+        let anchored_index = self
+            .tb_selection_anchor
+            .filter(|(index, _)| self.tb_state.selected() == Some(*index))
+            .and_then(|(_, item_id)| row_of(item_id));
+        // Select the expanded item if available, otherwise keep the selected item selected (i.e. even if
+        // it moved), or select first item if none selected
         let selected_item_index = self
             .exp_item
             .id
-            .and_then(|item_id| feed_items.iter().position(|item| item.id == item_id))
+            .and_then(row_of)
+            .or(anchored_index)
             .or_else(|| match self.tb_state.selected() {
-                None if !feed_items.is_empty() => Some(0),
+                None if !rows.is_empty() => Some(0),
                 current => current,
             });
+        // Scroll the list along with the anchored item (e.g. as items are merged or sorted above it), so
+        // that it stays where it was on screen
+        if let (Some((prev_index, _)), Some(index)) = (self.tb_selection_anchor, anchored_index)
+            && selected_item_index == Some(index)
+        {
+            let offset = self.tb_state.offset_mut();
+            *offset = (*offset + index).saturating_sub(prev_index);
+        }
         self.tb_state.select(selected_item_index);
+        self.sb_state = self.sb_state.position(Self::scrollbar_position(
+            &self.tb_state,
+            &self.tb_cum_row_heights,
+        ));
+
+        // While loading, the first row is not anchored (i.e. the newest item stays selected), unlike a row
+        // that was scrolled to
+        self.initial_load_done |= !self.is_loading();
+        self.tb_selection_anchor = selected_item_index
+            .filter(|i| self.initial_load_done || *i > 0)
+            .and_then(|i| Some((i, rows.get(i)?.item()?.id)));
+
+        // The table is only given the visible rows, so it is rendered from a state relative to the first of
+        // them, in place of `tb_state` (which is relative to every row)
+        let (start, end) = Self::row_bounds(
+            &self.tb_cum_row_heights,
+            self.tb_state.selected(),
+            self.tb_state.offset(),
+            tb_area.height,
+        );
+        *self.tb_state.offset_mut() = start;
+        let mut visible_tb_state = TableState::default()
+            .with_selected(self.tb_state.selected().map(|i| i.saturating_sub(start)));
+        let tb_rows: Vec<Row> = (start..end)
+            .map(|i| {
+                let tb_row = match rows[i] {
+                    ListRow::Group {
+                        label,
+                        item_count,
+                        collapsed,
+                    } => draw_group_row(label, item_count, collapsed, &self.config.theme),
+                    ListRow::Item(feed_item) => {
+                        let title_lines = feed_item.title.as_ref().map(|title| {
+                            self.tb_wrapped_titles.get_or_wrap(
+                                feed_item.id,
+                                title,
+                                tb_col_areas[0].width as usize,
+                                row_options.min_wrap_width,
+                            )
+                        });
+                        let pub_date_lines = self.tb_wrapped_pub_dates.get_or_wrap(
+                            feed_item,
+                            tb_col_areas[1].width as usize,
+                            row_options,
+                        );
+                        feed_item.draw_row(
+                            tb_col_areas[0].width as usize,
+                            &row_state(i, feed_item),
+                            title_lines,
+                            pub_date_lines,
+                            row_options,
+                        )
+                    }
+                };
+                let tb_row_btm_margin = (i != rows.len().saturating_sub(1)) as u16;
+                tb_row.bottom_margin(tb_row_btm_margin)
+            })
+            .collect();
 
         let table = Table::new(tb_rows, tb_col_layout)
-            .highlight_symbol(span!(tb_hl_symbol).magenta())
+            .highlight_symbol(span!(tb_hl_symbol).fg(self.config.theme.highlight))
             .highlight_spacing(HighlightSpacing::Always)
             .column_spacing(tb_col_spacing);
 
@@ -509,59 +3221,441 @@ impl FeedWidget {
             .thumb_symbol("▐")
             .thumb_style(Color::DarkGray);
 
-        frame.render_stateful_widget(table, tb_area, &mut self.tb_state);
-        frame.render_stateful_widget(scrollbar, sb_area, &mut self.sb_state);
+        self.tb_viewport_height = tb_area.height;
+        self.tb_area = tb_area;
+        frame.render_stateful_widget(table, tb_area, &mut visible_tb_state);
+        if show_scrollbar {
+            frame.render_stateful_widget(scrollbar, sb_area, &mut self.sb_state);
+        }
+    }
+}
+
+// A row of the list, i.e. either the header of a group of items or an item
+enum ListRow<'a> {
+    Group {
+        label: &'a str,
+        item_count: usize,
+        collapsed: bool,
+    },
+    Item(&'a FeedItem),
+}
+
+impl<'a> ListRow<'a> {
+    fn item(&self) -> Option<&'a FeedItem> {
+        match self {
+            ListRow::Item(item) => Some(item),
+            ListRow::Group { .. } => None,
+        }
+    }
+}
+
+const GROUP_ROW_HEIGHT: u16 = 1;
+
+fn draw_group_row(label: &str, item_count: usize, collapsed: bool, theme: &Theme) -> Row<'static> {
+    let marker = if collapsed { "▸" } else { "▾" };
+    let row = Row::new(vec![
+        line![
+            span!("{} ", marker).dim(),
+            span!(label.to_owned()).fg(theme.highlight).bold()
+        ],
+        line!(format!("{} items", item_count)).dim().right_aligned(),
+    ]);
+    row.height(GROUP_ROW_HEIGHT)
+}
+
+// State of an item shown by its row of the list
+#[derive(Clone, Copy)]
+struct RowState {
+    is_read: bool,
+    is_new: bool,
+    // Not in the feed in any previous session, whether or not it has been read since (unlike `is_new`)
+    is_unseen: bool,
+    is_bookmarked: bool,
+    // Label the source above the item, i.e. the first item of a run of items from the same source
+    show_source_label: bool,
+}
+
+// Wrapped titles of the items of the list, kept across frames as wrapping every title of a long list on
+// every frame (e.g. while scrolling) adds up. Titles are wrapped for each width they are drawn at (e.g.
+// with and without the scrollbar), and wrapped again once the list is resized
+#[derive(Default)]
+struct WrappedTitles {
+    area_width: u16,
+    lines: HashMap<(NonZeroU64, usize), Vec<String>>,
+}
+
+impl WrappedTitles {
+    fn invalidate_if_resized(&mut self, area_width: u16) {
+        if self.area_width != area_width {
+            self.area_width = area_width;
+            self.lines.clear();
+        }
+    }
+
+    // The title of an item wrapped (or truncated, below `min_wrap_width`) to `width`, where the title of an
+    // item never changes as it is part of the item's id
+    fn get_or_wrap(
+        &mut self,
+        item_id: NonZeroU64,
+        title: &str,
+        width: usize,
+        min_wrap_width: usize,
+    ) -> &[String] {
+        self.lines
+            .entry((item_id, width))
+            .or_insert_with(|| wrap_or_truncate_then_apply(title, width, min_wrap_width, |l| l))
+    }
+}
+
+// Publish date labels of the items of the list as wrapped to the width of their column, kept for a few
+// seconds as formatting the relative dates (e.g. "3 hours ago") of every row of a long list on every frame
+// adds up, whereas they only change every minute or so
+#[derive(Default)]
+struct WrappedPubDates {
+    wrapped_at: Option<Instant>,
+    lines: HashMap<(NonZeroU64, usize, bool), Vec<String>>,
+}
+
+impl WrappedPubDates {
+    const MAX_AGE: Duration = Duration::from_secs(5);
+
+    fn invalidate_if_stale(&mut self) {
+        if self
+            .wrapped_at
+            .is_none_or(|wrapped_at| wrapped_at.elapsed() >= Self::MAX_AGE)
+        {
+            self.wrapped_at = Some(Instant::now());
+            self.lines.clear();
+        }
+    }
+
+    fn get_or_wrap(&mut self, item: &FeedItem, width: usize, options: RowOptions) -> &[String] {
+        self.lines
+            .entry((item.id, width, options.absolute_date))
+            .or_insert_with(|| {
+                let label = item.pub_date_label(options.absolute_date);
+                wrap_or_truncate_then_apply(&label, width, options.min_wrap_width, |l| l)
+            })
+    }
+}
+
+// Options shared by every row of the list (unlike the per-item state passed to `FeedItem::draw_row`)
+#[derive(Clone, Copy)]
+struct RowOptions {
+    absolute_date: bool,
+    show_url: bool,
+    // Tag every item with its source after its title
+    source_tag: bool,
+    min_wrap_width: usize,
+    theme: Theme,
+}
+
+// Text of the lines of an item's row (i.e. before they are styled), which both `FeedItem::row_height` and
+// `FeedItem::draw_row` are derived from, so that the height of a row always matches the row as drawn
+struct RowLayout<'a> {
+    title_lines: Cow<'a, [String]>,
+    // Whether the source tag follows the title on its last line, rather than being on `tag_lines`
+    tag_inline: bool,
+    tag_lines: Vec<String>,
+    show_url: bool,
+    show_source_label: bool,
+    pub_date_lines: &'a [String],
+    show_badges: bool,
+}
+
+impl RowLayout<'_> {
+    fn height(&self) -> u16 {
+        let content_height = self.show_source_label as usize
+            + self.title_lines.len()
+            + self.tag_lines.len()
+            + self.show_url as usize;
+        let pub_date_height = self.pub_date_lines.len() + self.show_badges as usize;
+        max(content_height, pub_date_height) as u16
     }
 }
 
 impl FeedItem {
-    fn draw_row(&self, col_areas: &[Rect; 2]) -> (Row<'_>, u16) {
-        let [label_width, pub_date_width] = col_areas.map(|area| area.width);
-
-        let w_title = {
-            let title_width = label_width as usize;
-            match &self.title {
-                Some(title_text) => {
-                    wrap_then_apply(&title_text, title_width, |l| line!(l).white().bold())
-                }
-                None => wrap_then_apply(&"untitled".to_string(), title_width, |l| {
-                    line!(l).dim().bold()
-                }),
-            }
+    // `title_lines` is the item's title as wrapped to the width of the first column, if it has a title, and
+    // `pub_date_lines` its publish date label as wrapped to the width of the second column
+    fn row_layout<'a>(
+        &self,
+        label_width: usize,
+        state: &RowState,
+        title_lines: Option<&'a [String]>,
+        pub_date_lines: &'a [String],
+        options: RowOptions,
+    ) -> RowLayout<'a> {
+        let title_lines = match title_lines {
+            Some(title_lines) => Cow::Borrowed(title_lines),
+            None => Cow::Owned(Self::untitled_lines(label_width, options.min_wrap_width)),
+        };
+        // The tag follows the title on its last line if it fits, and is on lines of its own otherwise
+        let tag_inline = options.source_tag
+            && (title_lines.last()).is_some_and(|last_line| {
+                self.source_tag_fits(Line::from(last_line.as_str()).width(), label_width)
+            });
+        let tag_lines = match options.source_tag && !tag_inline {
+            true => wrap_or_truncate_then_apply(
+                &self.source,
+                label_width,
+                options.min_wrap_width,
+                |l| l,
+            ),
+            false => Vec::new(),
         };
+        RowLayout {
+            title_lines,
+            tag_inline,
+            tag_lines,
+            show_url: options.show_url && self.url.is_some(),
+            show_source_label: state.show_source_label,
+            pub_date_lines,
+            show_badges: state.is_bookmarked || state.is_new || state.is_unseen,
+        }
+    }
+
+    // Height of the item's row as drawn by `draw_row`, without drawing it, as the heights of every row are
+    // needed whereas only the visible rows are drawn
+    fn row_height(
+        &self,
+        label_width: usize,
+        state: &RowState,
+        title_lines: Option<&[String]>,
+        pub_date_lines: &[String],
+        options: RowOptions,
+    ) -> u16 {
+        self.row_layout(label_width, state, title_lines, pub_date_lines, options)
+            .height()
+    }
+
+    fn untitled_lines(width: usize, min_wrap_width: usize) -> Vec<String> {
+        wrap_or_truncate_then_apply("untitled", width, min_wrap_width, |l| l)
+    }
+
+    // Whether the source tag fits after the title on its last line, rather than on a line of its own
+    fn source_tag_fits(&self, last_line_width: usize, width: usize) -> bool {
+        last_line_width + self.source_tag().width() <= width
+    }
+
+    fn source_tag(&self) -> Span<'static> {
+        span!(format!(" · {}", self.source)).dim().italic()
+    }
+
+    fn pub_date_label(&self, absolute_date: bool) -> String {
+        match self.pub_date {
+            Some(pub_date) if absolute_date => pub_date.format(SHORT_TIMESTAMP_FMT).to_string(),
+            Some(pub_date) => HumanTime::from(pub_date).to_string(),
+            None => "no date".to_string(),
+        }
+    }
 
-        let content_lines = match self.url {
-            Some(ref url) => chain(w_title, vec![line!(url).dim()]).collect(),
-            None => w_title,
+    // Draw the item's row as laid out by `row_layout` (see there for the arguments)
+    fn draw_row(
+        &self,
+        label_width: usize,
+        state: &RowState,
+        title_lines: Option<&[String]>,
+        pub_date_lines: &[String],
+        options: RowOptions,
+    ) -> Row<'_> {
+        let layout = self.row_layout(label_width, state, title_lines, pub_date_lines, options);
+        let RowState {
+            is_read,
+            is_new,
+            is_unseen,
+            is_bookmarked,
+            ..
+        } = *state;
+        let theme = options.theme;
+
+        // Read items are dimmed so that unread items stand out
+        let mut w_title: Vec<_> = (layout.title_lines.iter())
+            .map(|l| match (title_lines.is_some(), is_read) {
+                (false, _) => line!(l.clone()).dim().bold(),
+                (true, true) => line!(l.clone()).fg(theme.title),
+                (true, false) => line!(l.clone()).bold().fg(theme.title),
+            })
+            .collect();
+        if layout.tag_inline
+            && let Some(last_line) = w_title.last_mut()
+        {
+            last_line.push_span(self.source_tag());
+        }
+        let w_tag = (layout.tag_lines.iter()).map(|l| line!(l.clone()).dim().italic());
+
+        let w_url = match self.url {
+            Some(_) if !layout.show_url => None,
+            // Link-only items are opened directly instead of being expanded, as there is nothing to read
+            Some(ref url) if self.is_link_only() => {
+                Some(line![span!("→ ").cyan(), span!(url).fg(theme.url).dim()])
+            }
+            Some(ref url) => Some(line!(url).fg(theme.url).dim()),
+            None => None,
         };
+        let w_source_label =
+            (layout.show_source_label).then(|| line!(self.source.as_str()).dim().italic());
+        let content_lines: Vec<_> = chain!(w_source_label, w_title, w_tag, w_url).collect();
 
-        let w_pub_date = wrap_then_apply(
-            &HumanTime::from(self.pub_date).to_string(),
-            pub_date_width as usize,
-            |l| line!(l).yellow().italic().right_aligned(),
-        );
+        let w_pub_date = (layout.pub_date_lines.iter())
+            .map(|l| line!(l.clone()).italic().right_aligned())
+            .map(|l| match self.pub_date {
+                Some(_) => l.fg(theme.pub_date),
+                None => l.dim(),
+            });
+        // Bookmarked items are starred next to the "new" badge (if any), followed by a dot for items that
+        // appeared since the last session
+        let badges = [
+            is_bookmarked.then(|| span!("★").yellow()),
+            is_new.then(|| span!("new").green().bold()),
+            is_unseen.then(|| span!("•").light_blue().bold()),
+        ];
+        let badges: Vec<_> =
+            Itertools::intersperse(badges.into_iter().flatten(), span!(" ")).collect();
+        let badges = (layout.show_badges).then(|| Line::from(badges).right_aligned());
+        let w_pub_date: Vec<_> = chain(w_pub_date, badges).collect();
 
-        let row_height = max(content_lines.len(), w_pub_date.len()) as u16;
-        (
-            row![content_lines, w_pub_date].height(row_height),
-            row_height,
-        )
+        row![content_lines, w_pub_date].height(layout.height())
     }
 }
 
+// An image of the expanded item's content, drawn over the blank lines kept for it
+#[derive(Clone)]
+struct ContentImage {
+    // Index of the first line kept for the image
+    line: usize,
+    url: String,
+    alt: String,
+    width: u16,
+    height: u16,
+}
+
 #[derive(Clone, Default)]
 struct ExpandedItemWidget {
     id: Option<NonZeroU64>,
     cached_render_content: Option<Vec<Line<'static>>>,
+    // Whether the cached content includes the (translation, summary) of the item
+    cached_transforms: (bool, bool),
+    // Whether the cached content was rendered while the item's content was still out-of-line
+    cached_content_external: bool,
+    // Number of words of the cached content (before wrapping), for the reading time estimate
+    cached_word_count: usize,
+    // Images of the cached content, and the number of downloaded images when it was cached (as images
+    // take up more lines once downloaded)
+    cached_images: Vec<ContentImage>,
+    cached_images_downloaded: usize,
+    // URLs of the cached content, drawn as hyperlinks (see `hyperlinks`)
+    cached_urls: Vec<WrappedUrl>,
+    // Parts of the URLs drawn in the last render (area, URL), i.e. those scrolled into view
+    hyperlinks: Vec<(Rect, String)>,
+    show_translation: bool,
+    show_summary: bool,
 
     curr_content_render_width: Option<u16>,
     curr_content_render_height: Option<u16>,
 
     scroll_offset: usize,
     sb_state: ScrollbarState,
+    // Lines of the content being selected to be copied (start, end), from the line the selection was
+    // started at to the line it was moved to (i.e. `end` may be above `start`)
+    selection: Option<(usize, usize)>,
 }
 
 impl ExpandedItemWidget {
+    // Average reading speed (in words per minute) of the reading time estimate
+    const READING_WPM: usize = 200;
+    // Categories shown below the metadata, the rest of which are only counted (e.g. "+3")
+    const MAX_CATEGORIES: usize = 5;
+
+    // Enclosure as e.g. "Audio · audio/mpeg · 24.3 MB · p play · w download", along with the state of its
+    // download
+    fn enclosure_line(
+        enclosure: &Enclosure,
+        download: Option<&EnclosureDownload>,
+    ) -> Line<'static> {
+        let details = chain!(
+            enclosure.mime_type.clone(),
+            enclosure.length.map(format_size)
+        );
+        let download_span = match download {
+            None => span!("w download").dim(),
+            Some(EnclosureDownload::InProgress) => span!("downloading…").dim().italic(),
+            Some(EnclosureDownload::Done(path)) => span!("saved to {}", path.display()).green(),
+            Some(EnclosureDownload::Failed) => span!("download failed (w to retry)").red(),
+        };
+        Line::from_iter(chain!(
+            [span!(enclosure.kind()).cyan().bold()],
+            details.map(|detail| span!(" · {}", detail).dim()),
+            [span!(" · p play · ").dim(), download_span]
+        ))
+    }
+
+    // Chips of the categories that fit within `width` (and `MAX_CATEGORIES`), followed by the number of
+    // those that do not
+    fn category_chips(categories: &[String], width: usize) -> Vec<Span<'static>> {
+        // Wide enough for e.g. " +12"
+        const MORE_LABEL_W: usize = 4;
+        let mut spans = Vec::new();
+        let mut used_w = 0;
+        let mut shown_count = 0;
+        for category in categories.iter().take(Self::MAX_CATEGORIES) {
+            let gap_w = usize::from(shown_count > 0);
+            let chip_w = category.chars().count() + 2;
+            let reserved_w = match shown_count + 1 < categories.len() {
+                true => MORE_LABEL_W,
+                false => 0,
+            };
+            if used_w + gap_w + chip_w + reserved_w > width {
+                break;
+            }
+            if gap_w > 0 {
+                spans.push(span!(" "));
+            }
+            // Reversed rather than only colored, so that chips remain chips without colors
+            spans.push(span!(" {} ", category).cyan().reversed());
+            used_w += gap_w + chip_w;
+            shown_count += 1;
+        }
+        if shown_count < categories.len() {
+            spans.push(span!(" +{}", categories.len() - shown_count).dim());
+        }
+        spans
+    }
+
+    // Wrap the content of a newly expanded item ahead of its first render using the last known content
+    // area, so that the first frame of the expanded view is already complete
+    fn prewrap(
+        &mut self,
+        items: &[FeedItem],
+        content_area: Option<Rect>,
+        config: &Config,
+        images: Option<&ImageCache>,
+    ) {
+        let Some(content_area) = content_area else {
+            return;
+        };
+        if let Some(feed_item) = self
+            .id
+            .and_then(|id| items.iter().find(|item| item.id == id))
+        {
+            self.sync_content_and_viewport(
+                feed_item,
+                TransformedContent::default(),
+                content_area,
+                config,
+                images,
+            );
+        }
+    }
+
+    fn content_area(&self) -> Option<Rect> {
+        Some(Rect::new(
+            0,
+            0,
+            self.curr_content_render_width?,
+            self.curr_content_render_height?,
+        ))
+    }
+
     fn get_max_scroll_offset(&self) -> usize {
         self.cached_render_content
             .as_ref()
@@ -570,6 +3664,9 @@ impl ExpandedItemWidget {
     }
 
     fn scroll(&mut self, delta: isize) {
+        if self.selection.is_some() {
+            return self.move_selection(delta);
+        }
         match delta {
             isize::MIN => self.scroll_offset = 0,
             isize::MAX => self.scroll_offset = self.get_max_scroll_offset(),
@@ -584,34 +3681,143 @@ impl ExpandedItemWidget {
         self.sb_state = self.sb_state.position(self.scroll_offset);
     }
 
-    fn render(&mut self, frame: &mut Frame, area: Rect, feed_item: &FeedItem) {
+    // Start selecting lines from the top visible line, or stop selecting
+    fn toggle_selection(&mut self) {
+        self.selection = match self.selection {
+            Some(_) => None,
+            None => Some((self.scroll_offset, self.scroll_offset)),
+        };
+    }
+
+    // Lines of the selection in order, within the content (which may have since been re-wrapped shorter)
+    fn selected_lines(&self) -> Option<RangeInclusive<usize>> {
+        let (start, end) = self.selection?;
+        let last_line = self.cached_render_content.as_ref()?.len().checked_sub(1)?;
+        Some(min(start, end).min(last_line)..=max(start, end).min(last_line))
+    }
+
+    // Move the end of the selection by `delta` lines, scrolling the content along to keep it in view
+    fn move_selection(&mut self, delta: isize) {
+        let last_line = self
+            .cached_render_content
+            .as_ref()
+            .map_or(0, |content| content.len().saturating_sub(1));
+        let Some((_, end)) = &mut self.selection else {
+            return;
+        };
+        *end = match delta {
+            isize::MIN => 0,
+            isize::MAX => last_line,
+            delta => end.saturating_add_signed(delta).min(last_line),
+        };
+        let page_height = (self.curr_content_render_height.unwrap_or(0) as usize).max(1);
+        self.scroll_offset = self
+            .scroll_offset
+            .clamp((*end + 1).saturating_sub(page_height), *end)
+            .min(self.get_max_scroll_offset());
+        self.sb_state = self.sb_state.position(self.scroll_offset);
+    }
+
+    // Scroll by `halves` half pages of the content's height (at least a line)
+    fn scroll_page(&mut self, halves: isize) {
+        let page_height = self.curr_content_render_height.unwrap_or(0) as isize;
+        self.scroll(halves.signum() * (page_height * halves.abs() / 2).max(1));
+    }
+
+    fn render(
+        &mut self,
+        frame: &mut Frame,
+        area: Rect,
+        feed_item: &FeedItem,
+        transformed: TransformedContent,
+        config: &Config,
+        images: Option<&ImageCache>,
+    ) {
         let outline_block = Block::bordered()
             .border_type(BorderType::Rounded)
             .border_style(Color::DarkGray)
-            .padding(Padding::symmetric(2, 1));
+            .padding(config.content_padding);
 
         let render_area = outline_block.inner(area);
         // Dynamically wrap the title to calculate height required for full visibility.
         // `Paragraph::wrap` is not enough to guarantee visibility if the allocated area is smaller than
         // the wrapped text. Therefore, we will need to dynamically set the height of the render area for the title
         let title_lines = match &feed_item.title {
-            Some(title_text) => wrap_then_apply(title_text, render_area.width as usize, |l| {
-                line!(l).white().bold()
-            }),
+            Some(title_text) => wrap_or_truncate_then_apply(
+                title_text,
+                render_area.width as usize,
+                config.min_wrap_width,
+                |l| line!(l).bold(),
+            )
+            .into_iter()
+            .map(|l| l.fg(config.theme.title))
+            .collect(),
             None => vec![line!("untitled").dim().bold()],
         };
 
         let title_h = title_lines.len() as u16;
-        // Assume that metadata will only ever take up 2 lines. This is not ideal as there will be a
-        // breaking point where parts of metadata will be hidden if the width of the terminal is too small
-        let meta_h: u16 = 2;
+
+        // Cap the number of authors shown, as multi-author items (e.g. academic papers) would otherwise
+        // overflow the metadata area
+        let shown_authors = match config.max_authors {
+            0 => feed_item.authors.len(),
+            max_authors => min(max_authors, feed_item.authors.len()),
+        };
+        let mut author_spans = vec![];
+        if !feed_item.authors.is_empty() {
+            author_spans.push(span!("by ").dim());
+            for (i, author) in feed_item.authors.iter().take(shown_authors).enumerate() {
+                if i > 0 {
+                    author_spans.push(span!(", ").dim());
+                }
+                author_spans.push(span!(author).light_green().italic());
+            }
+            if shown_authors < feed_item.authors.len() {
+                author_spans.push(span!(" et al.").dim());
+            }
+        }
+
+        // Metadata takes up at least 2 lines (relative + absolute publish date). The authors are wrapped
+        // into the left half of the metadata area (above the reading time), so the height grows with the
+        // number of wrapped lines
+        let authors_text: String = author_spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        let authors_h = wrap_then_apply(
+            &authors_text,
+            (render_area.width / 2).max(1) as usize,
+            |_| (),
+        )
+        .len() as u16;
+        let has_authors = !author_spans.is_empty();
+        let meta_h: u16 = match has_authors {
+            true => max(2, authors_h + 1),
+            false => 2,
+        };
+
+        // The categories and enclosure (if any) are each on a line of their own below the metadata
+        let category_chips =
+            Self::category_chips(&feed_item.categories, render_area.width as usize);
+        let extra_lines: Vec<Line> = chain!(
+            (!category_chips.is_empty()).then(|| Line::from(category_chips)),
+            feed_item.enclosure.as_ref().map(|enclosure| {
+                Self::enclosure_line(enclosure, feed_item.enclosure_download.as_ref())
+            })
+        )
+        .collect();
+        // +1: padding between metadata and the extra lines
+        let extras_h = match extra_lines.len() as u16 {
+            0 => 0,
+            extra_lines_h => extra_lines_h + 1,
+        };
 
         let [header_area, _, content_area, _]: [Rect; 4] =
             // +1: padding between title and metadata
-            vertical![==(title_h + meta_h + 1), ==1, *=0, ==1].areas(render_area);
+            vertical![==(title_h + meta_h + extras_h + 1), ==1, *=0, ==1].areas(render_area);
 
-        let [title_area, _, meta_area]: [Rect; 3] =
-            vertical![==title_h, ==1, ==meta_h].areas(header_area);
+        let [title_area, _, meta_area, extras_area]: [Rect; 4] =
+            vertical![==title_h, ==1, ==meta_h, ==extras_h].areas(header_area);
 
         let [left_meta_area, right_meta_area]: [Rect; 2] = horizontal![==50%, ==50%]
             .flex(Flex::SpaceBetween)
@@ -619,42 +3825,139 @@ impl ExpandedItemWidget {
 
         frame.render_widget(outline_block, area);
         frame.render_widget(Text::from(title_lines), title_area);
+        frame.render_widget(Text::from_iter(chain!([line!()], extra_lines)), extras_area);
 
-        let pub_date_label = para_wrap!(text![
-            line!(HumanTime::from(feed_item.pub_date).to_string())
-                .yellow()
-                .italic(),
-            line!(feed_item.pub_date.format(LONG_TIMESTAMP_FMT).to_string()).dim()
-        ]);
+        let pub_date_label = match feed_item.pub_date {
+            Some(pub_date) => para_wrap!(text![
+                line!(HumanTime::from(pub_date).to_string())
+                    .fg(config.theme.pub_date)
+                    .italic(),
+                line!(pub_date.format(&config.time_format).to_string()).dim()
+            ]),
+            None => para_wrap!(text![line!("no date").italic().dim()]),
+        };
 
-        if !feed_item.authors.is_empty() {
-            let mut author_spans = vec![span!("by ").dim()];
-            for (i, author) in feed_item.authors.iter().enumerate() {
-                if i > 0 {
-                    author_spans.push(span!(", ").dim());
-                }
-                author_spans.push(span!(author).light_green().italic());
-            }
-            frame.render_widget(para_wrap!(text!(author_spans)), left_meta_area);
+        // The reading time is below the authors, or otherwise opposite the publish date
+        let [authors_area, reading_time_area] = match has_authors {
+            true => vertical![==authors_h, ==1].areas(left_meta_area),
+            false => [Rect::default(), right_meta_area],
+        };
+        if has_authors {
+            frame.render_widget(para_wrap!(text!(author_spans)), authors_area);
             frame.render_widget(pub_date_label.right_aligned(), right_meta_area);
         } else {
             frame.render_widget(pub_date_label.left_aligned(), left_meta_area);
         }
 
-        let [text_area, sb_area] = horizontal![*=1, ==2].areas(content_area);
+        let [mut text_area, sb_area] = horizontal![*=1, ==2].areas(content_area);
+        let mut show_scrollbar = true;
+
+        if config.auto_hide_scrollbar {
+            // Start from the layout (with or without the scrollbar gutter) used in the previous frame, and
+            // only switch (re-wrap) when the content no longer fits/overflows. This avoids re-wrapping the
+            // content back and forth between the two layouts every frame
+            let try_full_width = self.curr_content_render_width
+                == Some(Self::text_column(content_area, config).width);
+            let first_area = if try_full_width {
+                content_area
+            } else {
+                text_area
+            };
+            let first_area = Self::text_column(first_area, config);
+            let fits = self
+                .sync_content_and_viewport(feed_item, transformed, first_area, config, images)
+                .len()
+                <= content_area.height as usize;
+            show_scrollbar = !fits;
+            if !show_scrollbar {
+                text_area = content_area;
+            }
+        }
 
-        let content = self.sync_content_and_viewport(feed_item, text_area);
+        let text_area = Self::text_column(text_area, config);
+        // Selected lines are highlighted by reversing their colors
+        let selected_lines = self.selected_lines();
+        let content =
+            self.sync_content_and_viewport(feed_item, transformed, text_area, config, images);
         let content_height = content.len();
 
         let visible_content = content
             .into_owned()
             .into_iter()
+            .enumerate()
             .skip(self.scroll_offset)
             .take(text_area.height as usize)
+            .map(|(i, line)| match &selected_lines {
+                Some(selected_lines) if selected_lines.contains(&i) => line.reversed(),
+                _ => line,
+            })
             .collect::<Vec<_>>();
 
         frame.render_widget(Text::from(visible_content), text_area);
 
+        // Items without any content (not even a description) have nothing to read
+        if self.cached_word_count > 0 {
+            let reading_mins = self.cached_word_count.div_ceil(Self::READING_WPM);
+            let reading_time_label = line!(format!(
+                "{} word{} · {} min read",
+                self.cached_word_count,
+                if self.cached_word_count == 1 { "" } else { "s" },
+                reading_mins
+            ))
+            .dim();
+            let reading_time_label = match has_authors {
+                true => reading_time_label.left_aligned(),
+                false => reading_time_label.right_aligned(),
+            };
+            frame.render_widget(reading_time_label, reading_time_area);
+        }
+
+        let visible_lines = self.scroll_offset..self.scroll_offset + text_area.height as usize;
+        let visible_row = |line: usize| text_area.y + (line - visible_lines.start) as u16;
+
+        // The URLs scrolled into view are made hyperlinks once the frame is drawn (see `App::draw_frame`)
+        self.hyperlinks = (self.cached_urls.iter())
+            .flat_map(|url| url.parts.iter().map(move |part| (part, &url.url)))
+            .filter(|((line, column, _), _)| {
+                visible_lines.contains(line) && *column < text_area.width as usize
+            })
+            .map(|(&(line, column, width), url)| {
+                let area = Rect::new(
+                    text_area.x + column as u16,
+                    visible_row(line),
+                    (width as u16).min(text_area.width - column as u16),
+                    1,
+                );
+                (area, url.clone())
+            })
+            .collect();
+
+        // Images are only drawn once fully scrolled into view (as graphics cannot be cut off at the edge
+        // of the viewport), showing their alt text until then
+        for image in self.cached_images.iter().filter(|_| images.is_some()) {
+            let image_lines = image.line..image.line + image.height as usize;
+            if visible_lines.contains(&image_lines.start) && image_lines.end <= visible_lines.end {
+                let image_area = Rect::new(
+                    text_area.x,
+                    visible_row(image_lines.start),
+                    image.width,
+                    image.height,
+                );
+                if let Some(images) = images {
+                    images.render(&image.url, image_area, frame.buffer_mut());
+                }
+            } else if image_lines.start < visible_lines.end && visible_lines.start < image_lines.end
+            {
+                let alt_area = Rect::new(
+                    text_area.x,
+                    visible_row(image_lines.start.max(visible_lines.start)),
+                    text_area.width,
+                    1,
+                );
+                frame.render_widget(line!(alt_text(&image.alt)).dim().italic(), alt_area);
+            }
+        }
+
         let scrollbar = Scrollbar::default()
             .orientation(ScrollbarOrientation::VerticalRight)
             .begin_symbol(None)
@@ -666,36 +3969,163 @@ impl ExpandedItemWidget {
         let scrollable_height = content_height.saturating_sub(text_area.height as usize);
         self.sb_state = self.sb_state.content_length(scrollable_height);
 
-        frame.render_stateful_widget(scrollbar, sb_area, &mut self.sb_state);
+        // A scrollbar without anything to scroll is drawn as a full-height thumb, which misleadingly
+        // suggests that there is more content. The gutter is still kept (unless auto-hidden above) so that
+        // the content is not re-wrapped
+        if show_scrollbar && scrollable_height > 0 {
+            frame.render_stateful_widget(scrollbar, sb_area, &mut self.sb_state);
+        }
+
+        // How far the content is scrolled, on the bottom border (inside its corners), as in vim and less
+        let max_scroll_offset = self.get_max_scroll_offset();
+        if max_scroll_offset > 0 {
+            let scroll_label = match self.scroll_offset {
+                0 => "Top".to_string(),
+                offset if offset >= max_scroll_offset => "Bot".to_string(),
+                offset => format!("{}%", offset * 100 / max_scroll_offset),
+            };
+            let label_area = Rect::new(
+                area.x + 1,
+                area.bottom().saturating_sub(1),
+                area.width.saturating_sub(3),
+                1,
+            );
+            frame.render_widget(
+                line!(format!(" {} ", scroll_label)).dim().right_aligned(),
+                label_area,
+            );
+        }
+    }
+
+    // Column of `area` that the content is wrapped to, i.e. at most `--content-width` wide and centered
+    fn text_column(area: Rect, config: &Config) -> Rect {
+        match config.content_width {
+            Some(content_width) if content_width < area.width => {
+                let [column] = horizontal![==content_width].flex(Flex::Center).areas(area);
+                column
+            }
+            _ => area,
+        }
     }
 
     fn sync_content_and_viewport(
         &mut self,
         feed_item: &FeedItem,
+        transformed: TransformedContent,
         render_area: Rect,
-    ) -> Cow<[Line<'static>]> {
+        config: &Config,
+        images: Option<&ImageCache>,
+    ) -> Cow<'_, [Line<'static>]> {
         let render_width_changed = match self.curr_content_render_width {
             Some(curr_render_width) => curr_render_width != render_area.width,
             None => true,
         };
         let item_id_changed = self.id != Some(feed_item.id);
+        // Transformed content is only shown once it is available, which may be a few frames after it was
+        // requested since transforms are done asynchronously
+        let translation = transformed.translation.filter(|_| self.show_translation);
+        let summary = transformed.summary.filter(|_| self.show_summary);
+        let transforms_changed =
+            self.cached_transforms != (translation.is_some(), summary.is_some());
+        // Out-of-line content becomes inline once fetched
+        let content_external = feed_item.content_src.is_some();
+        let content_fetched = self.cached_content_external != content_external;
+        let images_downloaded = images.map_or(0, ImageCache::downloaded_count);
+        let images_changed = self.cached_images_downloaded != images_downloaded;
 
-        if render_width_changed || item_id_changed {
-            let content_to_render = feed_item
+        if render_width_changed
+            || item_id_changed
+            || transforms_changed
+            || content_fetched
+            || images_changed
+        {
+            let content_to_render = translation.or(feed_item
                 .content
                 .as_deref()
-                .or(feed_item.description.as_deref());
-
-            self.cached_render_content = content_to_render.map(|content| {
-                content
+                .or(feed_item.description.as_deref()));
+            self.cached_transforms = (translation.is_some(), summary.is_some());
+            self.cached_content_external = content_external;
+            self.cached_images_downloaded = images_downloaded;
+            // Link footnotes and images are not part of the text that is read
+            self.cached_word_count = content_to_render.map_or(0, |lines| {
+                let text_lines = &lines[..lines.len() - footnote_links(lines).len()];
+                text_lines
                     .iter()
-                    .flat_map(|l| {
-                        wrap_then_apply(l, render_area.width as usize, |l| {
-                            line!(l).fg(WARM_WHITE_RGB)
-                        })
-                    })
-                    .collect()
+                    .filter(|l| parse_image_line(l).is_none())
+                    .map(|l| l.split_whitespace().count())
+                    .sum()
+            });
+
+            let wrap_width = render_area.width as usize;
+            let wrap = if wrap_width < config.min_wrap_width {
+                truncate_then_apply
+            } else if config.preserve_indent {
+                wrap_indented_then_apply
+            } else {
+                wrap_then_apply
+            };
+            // The summary (if any) is shown as a TL;DR section above the content
+            let summary_lines = summary.into_iter().flat_map(|summary| {
+                chain!(
+                    [line!("TL;DR").bold().light_cyan()],
+                    summary
+                        .iter()
+                        .flat_map(move |l| wrap(l, wrap_width, |l| line!(l).light_cyan())),
+                    [line!()]
+                )
+            });
+
+            // Items with out-of-line content would otherwise appear empty (or only show a description)
+            let external_hint = content_external.then(|| {
+                [
+                    line!("Content is external, press f to fetch it")
+                        .dim()
+                        .italic(),
+                    line!(),
+                ]
             });
+
+            self.cached_images.clear();
+            let mut lines: Vec<Line<'static>> =
+                chain!(summary_lines, external_hint.into_iter().flatten()).collect();
+            for l in content_to_render.into_iter().flatten() {
+                let Some((src, alt)) = parse_image_line(l) else {
+                    lines.extend(
+                        wrap(l, wrap_width, Line::from)
+                            .into_iter()
+                            .map(|l| l.fg(config.theme.content)),
+                    );
+                    continue;
+                };
+                // Images that are not downloaded (yet) take up a line of alt text instead
+                let image = images.zip(resolve_src(src, feed_item.url.as_deref()));
+                let size = image.and_then(|(images, url)| {
+                    images.request(&url);
+                    let size = images.fit(&url, render_area.width, render_area.height)?;
+                    Some((url, size))
+                });
+                match size {
+                    Some((url, (width, height))) => {
+                        self.cached_images.push(ContentImage {
+                            line: lines.len(),
+                            url,
+                            alt: alt.to_owned(),
+                            width,
+                            height,
+                        });
+                        lines.extend(std::iter::repeat_n(line!(), height as usize));
+                    }
+                    None => lines.extend(wrap(&alt_text(alt), wrap_width, |l| {
+                        line!(l).dim().italic()
+                    })),
+                }
+            }
+            let line_texts: Vec<String> = (lines.iter())
+                .map(|l| l.spans.iter().map(|span| span.content.as_ref()).collect())
+                .collect();
+            self.cached_urls = find_wrapped_urls(&line_texts, wrap_width);
+            self.cached_render_content =
+                (content_to_render.is_some() || content_external).then_some(lines);
         }
 
         self.id = Some(feed_item.id);
@@ -706,52 +4136,239 @@ impl ExpandedItemWidget {
         self.scroll_offset = self.scroll_offset.min(self.get_max_scroll_offset());
         self.sb_state = self.sb_state.position(self.scroll_offset);
 
-        Cow::Borrowed(self.cached_render_content.as_ref().unwrap())
+        Cow::Borrowed(self.cached_render_content.as_deref().unwrap_or_default())
+    }
+}
+
+// Parse Atom content according to its `type`: "text" and non-HTML `text/*` media types are plain text,
+// whereas "html", "xhtml" and other media types are parsed as HTML. Content without a type is parsed as
+// HTML too, rather than as text, as many feeds omit the type of HTML content
+fn parse_atom_content(value: &str, content_type: Option<&str>) -> Vec<String> {
+    match content_type {
+        Some(content_type)
+            if content_type == "text"
+                || (content_type.starts_with("text/") && !content_type.contains("html")) =>
+        {
+            value.lines().map(str::to_owned).collect()
+        }
+        _ => try_parse_html(value),
     }
 }
 
 #[derive(Clone)]
 struct FeedItem {
     id: NonZeroU64,
+    // Name of the feed the item originates from
+    source: String,
+    // URL of the site of the feed the item originates from (e.g. the blog's home page), if it has one
+    source_url: Option<String>,
     title: Option<String>,
     url: Option<String>,
     authors: Vec<String>,
+    // Categories (i.e. tags) of the item, without duplicates
+    categories: Vec<String>,
+    enclosure: Option<Enclosure>,
+    // Download (with `w`) of the enclosure in this session, if any
+    enclosure_download: Option<EnclosureDownload>,
     description: Option<Vec<String>>,
     content: Option<Vec<String>>,
-    pub_date: DateTime<chrono::Local>,
+    // URL of out-of-line content (i.e. Atom `<content src="...">`) that is yet to be fetched
+    content_src: Option<String>,
+    // URLs of the links in the shown content (i.e. `content`, or otherwise `description`), by footnote
+    content_links: Vec<String>,
+    // Label of the group the item's feed is listed under in the feeds file
+    group: Option<String>,
+    // `None` for items without a (parsable) date, e.g. RSS items without `pubDate`
+    pub_date: Option<DateTime<chrono::Local>>,
+    // Position of the item in its feed
+    feed_index: usize,
+}
+
+// Media file attached to an item (e.g. a podcast episode), i.e. RSS `<enclosure>`, Atom
+// `<link rel="enclosure">` or the first JSON Feed attachment
+#[derive(Clone)]
+struct Enclosure {
+    url: String,
+    mime_type: Option<String>,
+    // In bytes. `None` when unknown, which feeds often give as 0
+    length: Option<u64>,
+}
+
+impl Enclosure {
+    // Kind of media shown for the enclosure, e.g. "Audio" for "audio/mpeg"
+    fn kind(&self) -> &'static str {
+        match self
+            .mime_type
+            .as_deref()
+            .and_then(|mime| mime.split_once('/'))
+        {
+            Some(("audio", _)) => "Audio",
+            Some(("video", _)) => "Video",
+            Some(("image", _)) => "Image",
+            _ => "Attachment",
+        }
+    }
+}
+
+#[derive(Clone)]
+enum EnclosureDownload {
+    InProgress,
+    Done(PathBuf),
+    Failed,
+}
+
+#[derive(PartialEq, Eq, Hash)]
+enum DuplicateKey {
+    Url(String),
+    TitleAndDate(String, DateTime<chrono::Local>),
 }
 
 impl FeedItem {
-    fn from_atom_entry(entry: &atom_syndication::Entry) -> Option<Self> {
-        let url = entry
+    // Whether the item is just a link (e.g. as in link blogs and aggregators), i.e. it has a URL but
+    // neither content nor a description
+    fn is_link_only(&self) -> bool {
+        let is_blank = |lines: &Option<Vec<String>>| {
+            lines
+                .as_ref()
+                .is_none_or(|lines| lines.iter().all(|line| line.trim().is_empty()))
+        };
+        self.url.is_some()
+            && self.content_src.is_none()
+            && is_blank(&self.content)
+            && is_blank(&self.description)
+    }
+
+    // Key by which the same article in overlapping feeds (e.g. a site's main feed and one of its category
+    // feeds) is told apart, which is its URL or otherwise its title and date. `None` for items that can
+    // not be told apart from others, which are never taken for duplicates
+    fn duplicate_key(&self) -> Option<DuplicateKey> {
+        if let Some(url) = &self.url {
+            return Some(DuplicateKey::Url(normalize_url(url)));
+        }
+        Some(DuplicateKey::TitleAndDate(
+            self.title.as_ref()?.trim().to_owned(),
+            self.pub_date?,
+        ))
+    }
+
+    // Categories as shown, trimmed and without blank or duplicate (ignoring case) categories
+    fn unique_categories<'a>(categories: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        categories
+            .into_iter()
+            .map(str::trim)
+            .filter(|category| !category.is_empty())
+            .unique_by(|category| category.to_lowercase())
+            .map(str::to_owned)
+            .collect()
+    }
+
+    fn update_content_links(&mut self) {
+        self.content_links = self
+            .content
+            .as_ref()
+            .or(self.description.as_ref())
+            .map(|lines| footnote_links(lines))
+            .unwrap_or_default();
+    }
+
+    fn from_atom_entry(entry: &atom_syndication::Entry, source: &str) -> Option<Self> {
+        // Links such as `self` (the entry's own feed document), `hub` (WebSub) and `edit` (AtomPub) are
+        // not meant for readers, and are never used as the item's URL. Neither are enclosures, which are
+        // the item's media rather than the item itself
+        let readable_links = || {
+            entry
+                .links
+                .iter()
+                .filter(|link| !matches!(link.rel.as_str(), "self" | "hub" | "edit" | "enclosure"))
+        };
+        let enclosure = entry
             .links
             .iter()
+            .find(|link| link.rel == "enclosure")
+            .map(|link| Enclosure {
+                url: link.href.to_owned(),
+                mime_type: link.mime_type.clone(),
+                length: link
+                    .length
+                    .as_deref()
+                    .and_then(|length| length.trim().parse().ok()),
+            });
+        let url = readable_links()
             .find(|link| link.rel == "alternate")
-            .or_else(|| entry.links.first())
+            .or_else(|| readable_links().next())
             .map(|link| link.href.to_owned());
 
-        let mut hasher = DefaultHasher::default();
-        (&entry.id, &entry.title.value, &entry.updated).hash(&mut hasher);
+        let content = entry.content();
+
+        // Hashed on the published date (where there is one) rather than the updated date, so that an edit
+        // of an entry is not taken for a new entry
+        let original_date = entry.published.as_ref().unwrap_or(&entry.updated);
+        let id = stable_hash((&entry.id, &entry.title.value, original_date));
 
         Some(Self {
-            id: NonZero::new(hasher.finish()).unwrap(),
+            id: NonZero::new(id).unwrap(),
+            source: source.to_owned(),
+            source_url: None,
             title: Some(entry.title.value.to_owned()),
             authors: entry
                 .authors
                 .iter()
                 .map(|author| author.name.to_owned())
                 .collect(),
-            description: entry.summary().map(|desc| try_parse_html(&desc.value)),
-            content: entry
-                .content()
-                .and_then(|c| c.value())
-                .map(|c_str| try_parse_html(c_str)),
+            // The label is the human-readable form of the term, where there is one
+            categories: Self::unique_categories(
+                entry
+                    .categories
+                    .iter()
+                    .map(|category| category.label().unwrap_or(category.term())),
+            ),
+            description: entry.summary().map(|desc| match desc.r#type {
+                TextType::Text => desc.value.lines().map(str::to_owned).collect(),
+                TextType::Html | TextType::Xhtml => try_parse_html(&desc.value),
+            }),
+            content: content.and_then(|c| {
+                c.value()
+                    .map(|value| parse_atom_content(value, c.content_type()))
+            }),
+            content_src: content
+                .filter(|c| c.value().is_none())
+                .and_then(|c| c.src())
+                .map(str::to_owned),
             url,
-            pub_date: entry.updated.into(),
+            enclosure,
+            enclosure_download: None,
+            pub_date: Self::atom_pub_date(entry),
+            // Set once all items of the feed are parsed
+            content_links: Vec::new(),
+            group: None,
+            feed_index: 0,
         })
     }
 
-    fn from_rss_item(item: &rss::Item) -> Option<Self> {
+    // Publish date of an Atom entry, which is when it was first published, falling back to when it was last
+    // updated. Dates that are missing are parsed as the Unix epoch (and dates that cannot be parsed fail
+    // the whole feed) by `atom_syndication`
+    fn atom_pub_date(entry: &atom_syndication::Entry) -> Option<DateTime<chrono::Local>> {
+        [entry.published.as_ref(), Some(&entry.updated)]
+            .into_iter()
+            .flatten()
+            .find(|date| date.timestamp() != 0)
+            .map(|date| (*date).into())
+    }
+
+    // Publish date of an RSS item, falling back to the Dublin Core date for items without a `pubDate`, or
+    // with one that cannot be parsed
+    fn rss_pub_date(item: &rss::Item) -> Option<DateTime<chrono::Local>> {
+        let dc_date = || {
+            item.dublin_core_ext
+                .iter()
+                .flat_map(|dcmi_ext| dcmi_ext.dates())
+                .find_map(|date| parse_feed_date(date))
+        };
+        item.pub_date().and_then(parse_feed_date).or_else(dc_date)
+    }
+
+    fn from_rss_item(item: &rss::Item, source: &str) -> Option<Self> {
         let mut authors = match item.dublin_core_ext {
             Some(ref dcmi_ext) => dcmi_ext
                 .creators()
@@ -763,21 +4380,496 @@ impl FeedItem {
         // Prioritise dublin core metadata (dcmi) over RSS metadata
         // This is just a guess, but it seems like the dcmi is more reliable and more widely used based
         // on the feeds I am subscribed to
-        if authors.is_empty() {
-            item.author().map(|author| authors.push(author.to_string()));
+        if authors.is_empty()
+            && let Some(author) = item.author()
+        {
+            authors.push(author.to_string());
         }
 
-        let mut hasher = DefaultHasher::default();
-        (&item.title, &item.description, &item.pub_date).hash(&mut hasher);
+        let id = stable_hash((&item.title, &item.description, &item.pub_date));
 
         Some(Self {
-            id: NonZero::new(hasher.finish()).unwrap(),
+            id: NonZero::new(id).unwrap(),
+            source: source.to_owned(),
+            source_url: None,
             title: item.title().map(str::to_string),
             url: item.link().map(str::to_string),
-            pub_date: DateTime::parse_from_rfc2822(item.pub_date()?).ok()?.into(),
+            pub_date: Self::rss_pub_date(item),
             description: item.description().map(try_parse_html),
             content: item.content().map(try_parse_html),
+            content_src: None,
+            content_links: Vec::new(),
+            group: None,
+            feed_index: 0,
             authors,
+            categories: Self::unique_categories(
+                item.categories().iter().map(|category| category.name()),
+            ),
+            enclosure: item.enclosure().map(|enclosure| Enclosure {
+                url: enclosure.url().to_owned(),
+                mime_type: Some(enclosure.mime_type().to_owned()).filter(|mime| !mime.is_empty()),
+                length: enclosure.length().trim().parse().ok(),
+            }),
+            enclosure_download: None,
+        })
+    }
+
+    fn from_json_item(item: &JsonFeedItem, source: &str) -> Option<Self> {
+        // `author` is only used by (older) feeds without `authors`
+        let authors = match item.authors.is_empty() {
+            true => item.author.as_slice(),
+            false => item.authors.as_slice(),
+        };
+
+        let id = stable_hash((item.id.to_string(), &item.title, &item.date_published));
+
+        // Items without a publish date fall back to their modification date, and items without either (or
+        // with one that cannot be parsed) are kept without a date, as RSS items are
+        let pub_date = (item.date_published.as_ref())
+            .or(item.date_modified.as_ref())
+            .and_then(|date| parse_feed_date(date));
+
+        Some(Self {
+            id: NonZero::new(id).unwrap(),
+            source: source.to_owned(),
+            source_url: None,
+            title: item.title.clone(),
+            url: item.url.clone().or_else(|| item.external_url.clone()),
+            authors: authors
+                .iter()
+                .filter_map(|author| author.name.clone())
+                .collect(),
+            categories: Self::unique_categories(item.tags.iter().map(String::as_str)),
+            enclosure: item.attachments.first().map(|attachment| Enclosure {
+                url: attachment.url.to_owned(),
+                mime_type: attachment.mime_type.clone(),
+                length: attachment.size_in_bytes.as_u64(),
+            }),
+            enclosure_download: None,
+            description: item
+                .summary
+                .as_ref()
+                .map(|summary| vec![summary.to_owned()]),
+            content: match (&item.content_html, &item.content_text) {
+                (Some(content_html), _) => Some(try_parse_html(content_html)),
+                (None, Some(content_text)) => {
+                    Some(content_text.lines().map(str::to_owned).collect())
+                }
+                (None, None) => None,
+            },
+            content_src: None,
+            content_links: Vec::new(),
+            group: None,
+            pub_date,
+            feed_index: 0,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write as _;
+
+    use flate2::{Compression, write::GzEncoder};
+    use ratatui::backend::TestBackend;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::{
+        keys::KeyBindings,
+        utils::{LONG_TIMESTAMP_FMT, ThemePreset},
+    };
+
+    const RSS_FEED: &str = r#"<?xml version="1.0"?>
+<rss version="2.0">
+  <channel>
+    <title>Example</title>
+    <link>https://example.com/</link>
+    <description>Example feed</description>
+    <item>
+      <title>First post</title>
+      <link>https://example.com/first</link>
+      <pubDate>Tue, 14 Oct 2025 09:00:00 GMT</pubDate>
+    </item>
+  </channel>
+</rss>"#;
+
+    fn parse_error(body: &str) -> String {
+        match Feed::parse(body.as_bytes()) {
+            Ok(_) => panic!("parsed as a feed: {}", body),
+            Err(e) => e.to_string(),
+        }
+    }
+
+    #[test]
+    fn html_page_is_reported_as_html() {
+        let body =
+            "<!DOCTYPE html>\n<html><head><title>Blog</title></head><body>Hello</body></html>";
+        assert!(parse_error(body).starts_with("served HTML, not a feed"));
+    }
+
+    fn gzip(body: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(body).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn gzip_compressed_feed_is_parsed() {
+        let compressed = gzip(RSS_FEED.as_bytes());
+
+        assert_eq!(gunzip_body(&compressed).as_ref(), RSS_FEED.as_bytes());
+        let Ok(Feed::Rss(channel)) = Feed::parse(&gunzip_body(&compressed)) else {
+            panic!("gzip-compressed RSS feed was not parsed as RSS");
+        };
+        assert_eq!(channel.items().len(), 1);
+        // Uncompressed bodies are passed through as they are
+        assert_eq!(
+            gunzip_body(RSS_FEED.as_bytes()).as_ref(),
+            RSS_FEED.as_bytes()
+        );
+    }
+
+    #[test]
+    fn json_feed_items_without_a_usable_date_are_kept() {
+        let body = r#"{
+  "version": "https://jsonfeed.org/version/1.1",
+  "title": "Example",
+  "items": [
+    {"id": "1", "title": "Undated", "content_text": "No date"},
+    {"id": "2", "title": "Misdated", "content_text": "Bad date", "date_published": "someday"},
+    {"id": "3", "title": "Modified", "content_text": "Date", "date_modified": "2025-10-14T09:00:00Z"}
+  ]
+}"#;
+        let Ok(Feed::Json(json_feed)) = Feed::parse(body.as_bytes()) else {
+            panic!("JSON Feed was not parsed as JSON Feed");
+        };
+        let items: Vec<_> = (json_feed.items.iter())
+            .filter_map(|item| FeedItem::from_json_item(item, "Example"))
+            .collect();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].pub_date, None);
+        assert_eq!(items[1].pub_date, None);
+        assert!(items[2].pub_date.is_some());
+    }
+
+    // The only entry of an Atom feed with `entry` (the elements inside `<entry>`)
+    fn atom_entry(entry: &str) -> atom_syndication::Entry {
+        let feed = format!(
+            r#"<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>Example</title>
+  <id>urn:example:feed</id>
+  <updated>2025-10-14T09:00:00Z</updated>
+  <entry>{}</entry>
+</feed>"#,
+            entry
+        );
+        let mut feed = atom_syndication::Feed::read_from(feed.as_bytes()).unwrap();
+        feed.entries.remove(0)
+    }
+
+    // The item of an Atom entry with `entry` (see `atom_entry`)
+    fn atom_item(entry: &str) -> FeedItem {
+        FeedItem::from_atom_entry(&atom_entry(entry), "Example").unwrap()
+    }
+
+    #[test]
+    fn atom_entry_url_skips_self_link() {
+        let item = atom_item(
+            r#"<id>urn:example:1</id>
+  <title>First post</title>
+  <updated>2025-10-14T09:00:00Z</updated>
+  <link rel="self" href="https://example.com/entries/1.atom"/>
+  <link href="https://example.com/first"/>"#,
+        );
+        assert_eq!(item.url.as_deref(), Some("https://example.com/first"));
+    }
+
+    #[test]
+    fn atom_entry_id_is_stable_across_updates() {
+        let item = |updated: &str| {
+            atom_item(&format!(
+                r#"<id>urn:example:1</id>
+  <title>First post</title>
+  <published>2025-10-14T09:00:00Z</published>
+  <updated>{}</updated>"#,
+                updated
+            ))
+        };
+        let original = item("2025-10-14T09:00:00Z");
+        let updated = item("2025-10-15T12:30:00Z");
+        assert_eq!(original.id, updated.id);
+        assert_eq!(original.pub_date, updated.pub_date);
+    }
+
+    // Response with `body`, and with `headers` (e.g. "Content-Encoding: gzip") besides the framing ones
+    fn http_response(status: &str, headers: &[&str], body: &[u8]) -> Vec<u8> {
+        let head = format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n{}\r\n",
+            status,
+            body.len(),
+            headers
+                .iter()
+                .map(|header| format!("{}\r\n", header))
+                .join("")
+        );
+        [head.as_bytes(), body].concat()
+    }
+
+    // Fetch a feed from a local server that answers every request (i.e. retries too) with `response`,
+    // returning the result along with the head of each request that was received
+    async fn fetch_served(response: Vec<u8>, max_retries: u32) -> (FetchResult, Vec<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/feed.xml", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::clone(&requests);
+        let server = tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buf = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let n = stream.read(&mut buf).await.unwrap();
+                    assert!(n > 0, "connection closed before the end of the request");
+                    request.extend_from_slice(&buf[..n]);
+                }
+                let request = String::from_utf8(request).unwrap();
+                received.lock().unwrap().push(request);
+                stream.write_all(&response).await.unwrap();
+            }
+        });
+
+        let feed = Feed::fetch(&Client::new(), &HttpCache::new(None), &url, max_retries).await;
+        server.abort();
+        let requests = requests.lock().unwrap().clone();
+        (feed, requests)
+    }
+
+    #[tokio::test]
+    async fn feed_request_prefers_feed_types() {
+        let response = http_response("200 OK", &[], RSS_FEED.as_bytes());
+        let (feed, requests) = fetch_served(response, 0).await;
+        assert!(matches!(feed, Ok(Feed::Rss(_))));
+
+        let accept = (requests[0].lines())
+            .find_map(|line| line.strip_prefix("accept: "))
+            .expect("request has an Accept header");
+        assert_eq!(accept, HTTP_ACCEPT);
+        assert!(accept.starts_with("application/rss+xml, application/atom+xml"));
+    }
+
+    #[tokio::test]
+    async fn gzip_encoded_response_is_decoded() {
+        let body = gzip(RSS_FEED.as_bytes());
+        let response = http_response("200 OK", &["Content-Encoding: gzip"], &body);
+        let (feed, requests) = fetch_served(response, 0).await;
+        let Ok(Feed::Rss(channel)) = feed else {
+            panic!("gzip-encoded RSS feed was not parsed as RSS");
+        };
+        assert_eq!(channel.items().len(), 1);
+
+        let accept_encoding = (requests[0].lines())
+            .find_map(|line| line.strip_prefix("accept-encoding: "))
+            .expect("request has an Accept-Encoding header");
+        assert!(accept_encoding.contains("gzip"));
+    }
+
+    // Status of the response that failed a fetch
+    fn error_status(feed: FetchResult) -> Option<StatusCode> {
+        let Err(e) = feed else {
+            panic!("error response was parsed as a feed");
+        };
+        (e.downcast_ref::<reqwest::Error>()).and_then(reqwest::Error::status)
+    }
+
+    #[tokio::test]
+    async fn error_status_fails_the_feed_before_parsing() {
+        let response = http_response("404 Not Found", &[], b"<html><body>Not Found</body></html>");
+        let (feed, _) = fetch_served(response, 0).await;
+        assert_eq!(error_status(feed), Some(StatusCode::NOT_FOUND));
+    }
+
+    // With a single retry, as each retry waits for `RETRY_BASE_DELAY` (or longer) first
+    #[tokio::test]
+    async fn server_errors_are_retried_up_to_max_retries() {
+        for (status, status_code) in [
+            (
+                "500 Internal Server Error",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            ("503 Service Unavailable", StatusCode::SERVICE_UNAVAILABLE),
+        ] {
+            let (feed, requests) = fetch_served(http_response(status, &[], b""), 1).await;
+            assert_eq!(error_status(feed), Some(status_code));
+            assert_eq!(requests.len(), 2, "{} was not retried once", status);
+        }
+    }
+
+    #[tokio::test]
+    async fn client_errors_are_not_retried() {
+        let (feed, requests) = fetch_served(http_response("404 Not Found", &[], b""), 2).await;
+        assert_eq!(error_status(feed), Some(StatusCode::NOT_FOUND));
+        assert_eq!(requests.len(), 1);
+    }
+
+    #[test]
+    fn status_errors_are_plain() {
+        assert_eq!(
+            FeedWidget::status_error(StatusCode::NOT_FOUND, 2),
+            "HTTP 404 Not Found (client error)"
+        );
+        assert_eq!(
+            FeedWidget::status_error(StatusCode::SERVICE_UNAVAILABLE, 2),
+            "HTTP 503 Service Unavailable (server error, after 2 retries)"
+        );
+        assert_eq!(
+            FeedWidget::status_error(StatusCode::SERVICE_UNAVAILABLE, 1),
+            "HTTP 503 Service Unavailable (server error, after 1 retry)"
+        );
+        assert_eq!(
+            FeedWidget::status_error(StatusCode::INTERNAL_SERVER_ERROR, 0),
+            "HTTP 500 Internal Server Error (server error)"
+        );
+    }
+
+    // Config as with the default arguments, without anything read from or written to the disk
+    fn test_config() -> Config {
+        Config {
+            feeds_file: PathBuf::new(),
+            feeds_url: None,
+            feeds_url_cache_file: PathBuf::new(),
+            http_cache_dir: None,
+            state_file: PathBuf::new(),
+            bookmarks_file: PathBuf::new(),
+            downloads_dir: PathBuf::new(),
+            tick_rate: None,
+            show_fps: false,
+            auto_hide_scrollbar: false,
+            wrap_scroll: false,
+            confirm_quit: false,
+            default_view: View::default(),
+            theme: Theme::preset(ThemePreset::default()),
+            show_images: false,
+            no_color: false,
+            hyperlinks: false,
+            keys: KeyBindings::default(),
+            timeout: None,
+            proxy: None,
+            max_retries: 0,
+            max_concurrency: 8,
+            refresh_interval: None,
+            follow_paging: false,
+            max_pages: 1,
+            max_items_per_feed: None,
+            max_items_total: None,
+            show_source: None,
+            absolute_dates: false,
+            hide_urls: false,
+            new_within: None,
+            time_format: LONG_TIMESTAMP_FMT.to_string(),
+            content_padding: Padding::horizontal(1),
+            content_width: None,
+            max_authors: 3,
+            min_wrap_width: 8,
+            preserve_indent: false,
+            strip_params: Vec::new(),
+            open_cmd: None,
+            translate_cmd: None,
+            summarize_cmd: None,
+            send_cmd: None,
+            send_content: false,
+            on_new_item_cmd: None,
+        }
+    }
+
+    // Whether the expanded view of an item with `content` draws a scrollbar thumb
+    fn draws_scrollbar_thumb(content: &str) -> bool {
+        let item = atom_item(&format!(
+            r#"<id>urn:example:1</id>
+  <title>First post</title>
+  <updated>2025-10-14T09:00:00Z</updated>
+  <content type="text">{}</content>"#,
+            content
+        ));
+        let config = test_config();
+        let mut exp_item = ExpandedItemWidget::default();
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 20)).unwrap();
+        terminal
+            .draw(|frame| {
+                let area = frame.area();
+                let transformed = TransformedContent::default();
+                exp_item.render(frame, area, &item, transformed, &config, None);
+            })
+            .unwrap();
+        (terminal.backend().buffer().content.iter()).any(|cell| cell.symbol() == "▐")
+    }
+
+    #[test]
+    fn scrollbar_is_hidden_when_content_fits() {
+        assert!(!draws_scrollbar_thumb("A short post."));
+        assert!(draws_scrollbar_thumb(&"A long post.\n".repeat(40)));
+    }
+
+    #[test]
+    fn row_bounds_keep_a_selected_row_taller_than_the_table() {
+        // Rows of 2, 10 and 2 lines, each but the last with a one line margin
+        let cum_row_heights = [3, 14, 16];
+        assert_eq!(
+            FeedWidget::row_bounds(&cum_row_heights, Some(1), 0, 5),
+            (1, 2)
+        );
+        assert_eq!(
+            FeedWidget::row_bounds(&cum_row_heights, Some(1), 2, 5),
+            (1, 2)
+        );
+        assert_eq!(
+            FeedWidget::row_bounds(&cum_row_heights, Some(2), 0, 5),
+            (2, 3)
+        );
+    }
+
+    // Text of an item's row, drawn in the list's two columns
+    fn draw_row_text(state: RowState) -> String {
+        let item = atom_item(
+            r#"<id>urn:example:1</id>
+  <title>First post</title>
+  <updated>2025-10-14T09:00:00Z</updated>"#,
+        );
+        let options = RowOptions {
+            absolute_date: true,
+            show_url: false,
+            source_tag: false,
+            min_wrap_width: 8,
+            theme: Theme::preset(ThemePreset::default()),
+        };
+        let title_lines = ["First post".to_string()];
+        let pub_date_lines = [item.pub_date_label(true)];
+        let row = item.draw_row(20, &state, Some(&title_lines), &pub_date_lines, options);
+
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 40, 3));
+        Widget::render(
+            Table::new([row], constraints![==20, ==20]),
+            buffer.area,
+            &mut buffer,
+        );
+        (buffer.content.iter()).map(|cell| cell.symbol()).collect()
+    }
+
+    #[test]
+    fn items_unseen_before_the_session_are_marked_even_when_read() {
+        let state = |is_read, is_unseen| RowState {
+            is_read,
+            is_new: false,
+            is_unseen,
+            is_bookmarked: false,
+            show_source_label: false,
+        };
+        assert!(draw_row_text(state(false, true)).contains('•'));
+        assert!(draw_row_text(state(true, true)).contains('•'));
+        assert!(!draw_row_text(state(false, false)).contains('•'));
+    }
+}
@@ -0,0 +1,73 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+};
+
+use itertools::Itertools;
+
+// An item saved for later, stored as a line of `<url> <title>` in the bookmarks file (URLs cannot contain
+// spaces, whereas titles can)
+pub(crate) struct Bookmark {
+    pub url: String,
+    pub title: String,
+}
+
+pub(crate) fn parse_bookmarks(content: &str) -> Vec<Bookmark> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (url, title) = line.split_once(' ').unwrap_or((line, ""));
+            Bookmark {
+                url: url.to_owned(),
+                title: title.trim().to_owned(),
+            }
+        })
+        .collect()
+}
+
+// A missing bookmarks file is the same as an empty one
+pub(crate) fn read_bookmarks(path: &Path) -> io::Result<Vec<Bookmark>> {
+    match fs::read_to_string(path) {
+        Ok(content) => Ok(parse_bookmarks(&content)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+// Bookmark `url`, or remove its bookmark if it is already bookmarked, keeping every other line as is.
+// Returns whether `url` is now bookmarked
+pub(crate) fn toggle_bookmark(path: &Path, url: &str, title: &str) -> io::Result<bool> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    let is_url = |line: &str| line.split_whitespace().next() == Some(url);
+    if content.lines().any(is_url) {
+        let mut new_content = content.lines().filter(|line| !is_url(line)).join("\n");
+        if content.ends_with('\n') && !new_content.is_empty() {
+            new_content.push('\n');
+        }
+        fs::write(path, new_content)?;
+        return Ok(false);
+    }
+
+    if let Some(parent_dir) = path.parent() {
+        fs::create_dir_all(parent_dir)?;
+    }
+    let mut bookmarks_file = fs::OpenOptions::new()
+        .append(true)
+        .create(true)
+        .open(path)?;
+    // Titles are kept on a single line, as each line is a bookmark
+    let title = title.split_whitespace().join(" ");
+    let separator = match content.is_empty() || content.ends_with('\n') {
+        true => "",
+        false => "\n",
+    };
+    writeln!(bookmarks_file, "{}{} {}", separator, url, title)?;
+    Ok(true)
+}
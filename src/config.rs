@@ -0,0 +1,127 @@
+use std::{path::PathBuf, time::Duration};
+
+use chrono::TimeDelta;
+use clap::ValueEnum;
+use ratatui::widgets::Padding;
+
+use crate::{keys::KeyBindings, utils::Theme};
+
+// View shown on launch
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub(crate) enum View {
+    // Aggregated list of items from every feed
+    #[default]
+    List,
+    // Aggregated list alongside the sources sidebar (with unread counts)
+    Overview,
+}
+
+// How items in the list are labelled with their source (i.e. the feed's title, or otherwise its host)
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub(crate) enum SourceLabels {
+    // A label above each run of consecutive items from the same source
+    Runs,
+    // A tag after the title of every item
+    Tags,
+}
+
+// Runtime options resolved from the CLI, shared by the app and its widgets
+#[derive(Clone, Debug)]
+pub(crate) struct Config {
+    pub feeds_file: PathBuf,
+    // Remote feeds list used instead of `feeds_file`, cached locally for when it cannot be fetched
+    pub feeds_url: Option<String>,
+    pub feeds_url_cache_file: PathBuf,
+    // Previously fetched feeds, reused when they are not modified (i.e. conditional requests), or `None`
+    // when the cache is bypassed
+    pub http_cache_dir: Option<PathBuf>,
+    // Per-item state (e.g. read items), persisted across sessions
+    pub state_file: PathBuf,
+    // Items saved for later with `b`, one `<url> <title>` per line
+    pub bookmarks_file: PathBuf,
+    // Directory that enclosures (e.g. podcast episodes) are downloaded to with `w`
+    pub downloads_dir: PathBuf,
+    // Interval between frames, or `None` for uncapped (i.e. draw whenever something changes)
+    pub tick_rate: Option<Duration>,
+    pub show_fps: bool,
+    // Hide scrollbars (and reclaim their gutter) when all content fits in the viewport
+    pub auto_hide_scrollbar: bool,
+    // Scroll past the last item of the list to the first one (and vice versa), rather than stopping there
+    pub wrap_scroll: bool,
+    // Ask to confirm quitting with the exit key too, rather than only when closing the list
+    pub confirm_quit: bool,
+    pub default_view: View,
+    // Colors of the preset chosen with `--theme`, overridden by those of the theme file
+    pub theme: Theme,
+    // Show the images of the expanded item (in terminals that support it) instead of their alt text
+    pub show_images: bool,
+    // Render with the terminal's default colors only, per `--no-color` or `NO_COLOR`
+    pub no_color: bool,
+    // Make the URLs of the expanded item clickable with OSC 8 hyperlinks, unless `--no-hyperlinks`
+    pub hyperlinks: bool,
+    // Keys of the rebindable actions (e.g. scrolling), from the keys file or the defaults
+    pub keys: KeyBindings,
+
+    // Timeout of each HTTP request, or `None` for no timeout
+    pub timeout: Option<Duration>,
+    // Proxy that every request goes through instead of that of the environment (e.g. HTTPS_PROXY)
+    pub proxy: Option<String>,
+    // Number of times a feed request that failed transiently (e.g. a 5xx response) is retried
+    pub max_retries: u32,
+    // Number of feeds fetched at once, beyond which feeds wait for an earlier fetch to finish
+    pub max_concurrency: usize,
+    // Interval between background refreshes of the feeds, or `None` to only refresh on demand
+    pub refresh_interval: Option<Duration>,
+
+    // Follow `rel="next"` links of paged feeds, fetching up to `max_pages` pages per feed
+    pub follow_paging: bool,
+    pub max_pages: usize,
+
+    // Maximum number of (newest) items kept per feed, and in total (i.e. across all feeds)
+    pub max_items_per_feed: Option<usize>,
+    pub max_items_total: Option<usize>,
+
+    // Label items in the list with their source, or `None` for no labels
+    pub show_source: Option<SourceLabels>,
+    // Show absolute instead of relative publish dates in the list on launch (toggleable at runtime)
+    pub absolute_dates: bool,
+    // Hide the item URLs in the list on launch (toggleable at runtime)
+    pub hide_urls: bool,
+    // Items are badged as new when published within this window, or otherwise (`None`) when they arrived
+    // since the last quit
+    pub new_within: Option<TimeDelta>,
+    // Format of the clock in the header and of absolute dates in the expanded view
+    pub time_format: String,
+
+    // Padding between the expanded view's border and its content
+    pub content_padding: Padding,
+    // Maximum width of the expanded view's content, which is centered when narrower than the view, or
+    // `None` for the full width
+    pub content_width: Option<u16>,
+    // Maximum number of authors shown in the expanded view (0 for no limit)
+    pub max_authors: usize,
+    // Text narrower than this is truncated to a single line instead of being wrapped
+    pub min_wrap_width: usize,
+    // Keep the leading whitespace of content lines on their wrapped continuation lines
+    pub preserve_indent: bool,
+
+    // Query parameters stripped from URLs before they are opened or copied (e.g. "utm_*"), empty to keep
+    // URLs as they are
+    pub strip_params: Vec<String>,
+    // Shell command that item URLs are opened with (with `{url}` replaced by the shell-quoted URL) instead
+    // of the default application
+    pub open_cmd: Option<String>,
+
+    // Shell command that receives an item's text on stdin and writes its translation to stdout
+    pub translate_cmd: Option<String>,
+    // Shell command that receives an item's text on stdin and writes a short summary to stdout
+    pub summarize_cmd: Option<String>,
+
+    // Shell command that items are sent to with `x` (e.g. a read-it-later CLI), receiving the item's URL
+    // on stdin, or its content with `send_content`
+    pub send_cmd: Option<String>,
+    pub send_content: bool,
+
+    // Shell command spawned for every item that a refresh newly merges into the feed
+    pub on_new_item_cmd: Option<String>,
+}
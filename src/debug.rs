@@ -1,4 +1,9 @@
-use std::time::{Duration, Instant};
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    time::{Duration, Instant},
+};
 
 use ratatui::{
     buffer::Buffer,
@@ -28,6 +33,12 @@ impl Default for FpsWidget {
     }
 }
 
+impl FpsWidget {
+    pub(crate) fn fps(&self) -> Option<f32> {
+        self.curr_fps
+    }
+}
+
 impl Widget for &mut FpsWidget {
     fn render(self, area: Rect, buf: &mut Buffer) {
         self.frame_count += 1;
@@ -74,3 +85,51 @@ impl Widget for &mut FpsWidget {
         }
     }
 }
+
+// Timings of every frame drawn, written as CSV (with `--perf-log`) to correlate jank with what caused
+// the frame, e.g. expanding a large item. Rows are buffered, and written out at the latest when the app
+// quits
+pub(crate) struct PerfLog {
+    writer: BufWriter<File>,
+    start: Instant,
+    last_frame: Option<Instant>,
+}
+
+impl PerfLog {
+    pub(crate) fn create(path: &Path) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "elapsed_ms,frame_ms,render_ms,draw_ms,trigger")?;
+        Ok(Self {
+            writer,
+            start: Instant::now(),
+            last_frame: None,
+        })
+    }
+
+    // Record a frame that started drawing at `frame_start`, where `render` is the time spent rendering the
+    // widgets and `draw` that plus writing the changes to the terminal. `frame_ms` (the time since the
+    // previous frame) is empty for the first frame
+    pub(crate) fn record(
+        &mut self,
+        frame_start: Instant,
+        render: Duration,
+        draw: Duration,
+        trigger: &str,
+    ) -> io::Result<()> {
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let frame_ms = self
+            .last_frame
+            .map(|last_frame| format!("{:.3}", ms(frame_start - last_frame)))
+            .unwrap_or_default();
+        self.last_frame = Some(frame_start);
+        writeln!(
+            self.writer,
+            "{:.3},{},{:.3},{:.3},\"{}\"",
+            ms(frame_start - self.start),
+            frame_ms,
+            ms(render),
+            ms(draw),
+            trigger.replace('"', "\"\"")
+        )
+    }
+}
@@ -0,0 +1,110 @@
+use std::error::Error;
+
+use html2text::{Element, Handle};
+use reqwest::{Client, Response, header::ACCEPT};
+use url::Url;
+
+use crate::{
+    app::{HTTP_ACCEPT, parse_feed_title},
+    utils::is_html_document,
+};
+
+// Content types of the feeds that webpages advertise (RSS, Atom and JSON Feed)
+const FEED_TYPES: [&str; 3] = [
+    "application/rss+xml",
+    "application/atom+xml",
+    "application/feed+json",
+];
+
+// A feed advertised by a webpage, e.g. `<link rel="alternate" type="application/rss+xml" href="/feed">`
+pub(crate) struct FeedLink {
+    pub url: Url,
+    pub title: Option<String>,
+}
+
+pub(crate) enum Discovery {
+    // The URL is a feed, with this title
+    Feed(String),
+    // The URL is a webpage, which advertises these feeds (if any)
+    Webpage(Vec<FeedLink>),
+}
+
+type CheckResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+// Fetch `url` to tell whether it is a feed or a webpage, and if it is a webpage, which feeds it advertises.
+// Fails if it cannot be fetched, or is neither
+pub(crate) async fn discover(http_client: &Client, url: &Url) -> CheckResult<Discovery> {
+    let (page_url, body) = fetch(http_client, url).await?;
+    match is_html_document(&body) {
+        true => Ok(Discovery::Webpage(feed_links(&body, &page_url))),
+        false => Ok(Discovery::Feed(parse_feed_title(&body, url.as_str())?)),
+    }
+}
+
+// Fetch `url` and return the title of the feed it is, failing if it is not a feed (e.g. a webpage)
+pub(crate) async fn feed_title(http_client: &Client, url: &Url) -> CheckResult<String> {
+    let (_, body) = fetch(http_client, url).await?;
+    parse_feed_title(&body, url.as_str())
+}
+
+// Body of the response to `url`, along with the URL it was served from after any redirects
+async fn fetch(http_client: &Client, url: &Url) -> reqwest::Result<(Url, Vec<u8>)> {
+    let http_resp = http_client
+        .get(url.clone())
+        .header(ACCEPT, HTTP_ACCEPT)
+        .send()
+        .await
+        .and_then(Response::error_for_status)?;
+    let page_url = http_resp.url().clone();
+    Ok((page_url, http_resp.bytes().await?.to_vec()))
+}
+
+// Feeds advertised by a webpage, in the order they appear on the page and without duplicates. Relative
+// links (e.g. "/feed.xml") are relative to `page_url`, the URL the page was served from
+fn feed_links(html: &[u8], page_url: &Url) -> Vec<FeedLink> {
+    let Ok(dom) = html2text::config::plain().parse_html(html) else {
+        return Vec::new();
+    };
+    let mut links = Vec::new();
+    collect_feed_links(&dom.document, page_url, &mut links);
+    links
+}
+
+fn collect_feed_links(node: &Handle, page_url: &Url, links: &mut Vec<FeedLink>) {
+    if let Element { name, attrs, .. } = &node.data
+        && name.local.as_ref() == "link"
+    {
+        let attrs = attrs.borrow();
+        let attr = |name: &str| {
+            attrs
+                .iter()
+                .find(|attr| attr.name.local.as_ref() == name)
+                .map(|attr| attr.value.trim())
+        };
+        // `rel` is a space-separated list, e.g. "alternate home"
+        let is_alternate = attr("rel").is_some_and(|rel| {
+            rel.split_ascii_whitespace()
+                .any(|rel| rel.eq_ignore_ascii_case("alternate"))
+        });
+        let is_feed = attr("type").is_some_and(|mime_type| {
+            FEED_TYPES
+                .iter()
+                .any(|feed_type| mime_type.eq_ignore_ascii_case(feed_type))
+        });
+        if is_alternate
+            && is_feed
+            && let Some(url) = attr("href").and_then(|href| page_url.join(href).ok())
+            && !links.iter().any(|link: &FeedLink| link.url == url)
+        {
+            links.push(FeedLink {
+                url,
+                title: attr("title")
+                    .filter(|title| !title.is_empty())
+                    .map(str::to_owned),
+            });
+        }
+    }
+    for child in node.children.borrow().iter() {
+        collect_feed_links(child, page_url, links);
+    }
+}
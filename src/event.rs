@@ -4,15 +4,125 @@ pub(crate) enum AppEvent {
     // isize::MAX respectively
     Scroll(isize),
 
+    // Scroll by a number of half pages (i.e. half the height of the list or of the expanded item's
+    // content), where positive is down and negative is up
+    ScrollPage(isize),
+
+    // Select the row of the list with the given index (e.g. the row clicked with the mouse)
+    Select(usize),
+
     // Enter a new view (e.g. a new screen or popup)
     Expand,
 
-    // Close a expanded/nested view (e.g. a popup or screen that is triggered by a parent widget)
+    // Close a expanded/nested view (e.g. a popup or screen that is triggered by a parent widget).
+    // Closing steps out one level at a time, until there is nothing left to close:
+    //
+    //   expanded item --Close--> list (filtered by a search) --Close--> list --Close--> quit prompt
+    //
+    // where the quit prompt takes every key until it is answered, with `y` (i.e. `Exit`) or with `n` and
+    // Escape (i.e. `CancelQuit`, back to where it was shown). `Exit` quits from anywhere, unless quits are
+    // to be confirmed (`--confirm-quit`), in which case it shows the quit prompt too
     Close,
 
+    // Re-open the previously expanded view (e.g. the last read item), akin to a browser's back button
+    Back,
+
     // Open the item in the default (external) application (e.g. browser)
     Open,
 
+    // Open the site of the item's feed (e.g. the blog's home page) in the default application
+    OpenSite,
+
+    // Open the enclosure of the item (e.g. a podcast episode) in the default application (e.g. a media
+    // player)
+    OpenEnclosure,
+
+    // Download the enclosure of the item into the downloads directory
+    DownloadEnclosure,
+
+    // Copy the URL of the item (e.g. the selected or expanded item) to the clipboard
+    CopyUrl,
+
+    // Start selecting lines of the expanded item's content (from its top visible line) to copy them, where
+    // scrolling moves the end of the selection, or stop selecting
+    ToggleLineSelection,
+    // Copy the selected lines of the expanded item to the clipboard, ending the selection
+    CopyLines,
+
+    // Open the link of the expanded item's content with the given (1-based) footnote number
+    OpenLink(usize),
+
+    // Pipe the item (e.g. the selected or expanded item) to the send command (`--send-cmd`), e.g. to save
+    // it to a read-it-later service
+    SendItem,
+
+    // Bookmark the item (e.g. the selected or expanded item) in the bookmarks file, or remove its bookmark
+    Bookmark,
+
+    // Mark every item shown in the list (i.e. only those matching the search, if any) as read
+    MarkAllRead,
+
+    // Toggle the item (e.g. the selected or expanded item) between read and unread
+    ToggleRead,
+
+    // Re-fetch the feeds, adding any new items to the list
+    Refresh,
+
+    // Toggle the sidebar overview of feed sources
+    ToggleSidebar,
+
+    // Collapse (or expand) the group of the selected item in the list
+    ToggleGroup,
+
+    // Toggle the item URLs shown under the titles in the list
+    ToggleUrls,
+
+    // Toggle showing only the unread items in the list
+    ToggleUnreadOnly,
+
+    // Toggle the panel listing the feeds that failed to load
+    ToggleErrors,
+
+    // Start typing a search query, filtering the list to the items whose title contains it
+    Search,
+    // Append a character to the search query
+    SearchInput(char),
+    // Delete the last character of the search query
+    SearchDelete,
+    // Stop typing the search query, keeping the list filtered (e.g. to navigate the matching items)
+    SearchSubmit,
+    // Clear the search query, restoring the full list
+    SearchClear,
+
+    // Cycle through the orders of items in the list (e.g. newest first, oldest first, by title)
+    CycleSort,
+
+    // Toggle between relative (e.g. "2 hours ago") and absolute publish dates in the list
+    ToggleDates,
+
+    // Export the configured feeds as OPML
+    ExportOpml,
+
+    // Toggle the translation of the expanded item (translated by an external command)
+    Translate,
+
+    // Toggle a short summary (TL;DR) of the expanded item (summarized by an external command)
+    Summarize,
+
+    // Fetch the out-of-line content of the expanded item (i.e. content only referenced by URL)
+    FetchContent,
+
+    // Copy the performance/debug stats (e.g. FPS, terminal size) to the clipboard
+    CopyDebugStats,
+
+    // Toggle the overlay listing every action and its keys
+    ToggleKeyHelp,
+
+    // Ask to confirm quitting, sent upstream by the active widget when there is nothing left to close
+    ConfirmQuit,
+    // Dismiss the quit prompt without quitting
+    CancelQuit,
+
     // Exit the application - akin to a kill switch
     Exit,
 }
@@ -0,0 +1,160 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use itertools::Itertools;
+use url::Url;
+
+// A line of a feeds file that is neither blank, a comment, an include, nor a valid URL
+pub(crate) struct InvalidLine {
+    pub path: PathBuf,
+    // 1-based, as shown in editors
+    pub line_no: usize,
+    pub line: String,
+}
+
+// An `@include` line of a feeds file that was skipped, along with the path it refers to (or, without a
+// path, the including file)
+pub(crate) enum SkippedInclude {
+    MissingPath(PathBuf),
+    Cyclic(PathBuf),
+    Unreadable(PathBuf),
+}
+
+impl fmt::Display for SkippedInclude {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::MissingPath(path) => write!(f, "@include without a path in {}", path.display()),
+            Self::Cyclic(path) => write!(f, "cyclic @include of {}", path.display()),
+            Self::Unreadable(path) => write!(f, "unreadable @include of {}", path.display()),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct FeedsList {
+    pub urls: Vec<String>,
+    pub invalid_lines: Vec<InvalidLine>,
+    pub skipped_includes: Vec<SkippedInclude>,
+    // Group label of each URL listed below a group header, keyed by URL
+    pub groups: HashMap<String, String>,
+}
+
+impl FeedsList {
+    fn push_url(&mut self, url: String, group: Option<&str>) {
+        if let Some(group) = group {
+            self.groups
+                .entry(url.clone())
+                .or_insert_with(|| group.to_owned());
+        }
+        self.urls.push(url);
+    }
+}
+
+// Parse a single line of a feeds list, where blank lines and comments (`# ...`) are skipped (`None`)
+pub(crate) fn parse_feed_line(line: &str) -> Option<Result<String, url::ParseError>> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+    Some(Url::parse(line).map(|url| url.to_string()))
+}
+
+// Label of a group header, i.e. a comment (e.g. `# News`) that groups the feeds listed below it
+pub(crate) fn parse_group_header(line: &str) -> Option<&str> {
+    let label = line.trim().strip_prefix('#')?.trim();
+    (!label.is_empty()).then_some(label)
+}
+
+// Parse a feeds list (one URL per line, optionally grouped under headers), skipping blank lines and lines
+// that are not valid URLs
+pub(crate) fn parse_feeds_list(content: &str) -> FeedsList {
+    let mut feeds_list = FeedsList::default();
+    let mut group = None;
+    for line in content.lines() {
+        if let Some(label) = parse_group_header(line) {
+            group = Some(label);
+        } else if let Some(Ok(url)) = parse_feed_line(line) {
+            feeds_list.push_url(url, group);
+        }
+    }
+    feeds_list
+}
+
+// Whether a feeds list already has `url`
+pub(crate) fn has_feed_url(content: &str, url: &str) -> bool {
+    content.lines().any(|line| line.trim() == url)
+}
+
+// Remove the lines of a feeds list that are `url`, keeping every other line (and whether the list ends
+// with a newline) as is. Returns `None` if no line is `url`
+pub(crate) fn remove_feed_url(content: &str, url: &str) -> Option<String> {
+    let is_url =
+        |line: &str| matches!(parse_feed_line(line), Some(Ok(line_url)) if line_url == url);
+    if !content.lines().any(is_url) {
+        return None;
+    }
+    let mut new_content = content.lines().filter(|line| !is_url(line)).join("\n");
+    if content.ends_with('\n') {
+        new_content.push('\n');
+    }
+    Some(new_content)
+}
+
+// Read the feed URLs of a feeds file, recursively resolving `@include <path>` lines (relative to the
+// including file's directory). Unreadable and cyclic includes are skipped (see `skipped_includes`), whereas `None`
+// is returned if `path` itself cannot be read. Feeds of an included file are in the group of the include
+// until the included file has group headers of its own
+pub(crate) fn read_feeds_file(path: &Path) -> Option<FeedsList> {
+    let mut feeds_list = FeedsList::default();
+    read_feeds_file_into(path, None, &mut HashSet::new(), &mut feeds_list)?;
+    Some(feeds_list)
+}
+
+fn read_feeds_file_into(
+    path: &Path,
+    include_group: Option<&str>,
+    visited: &mut HashSet<PathBuf>,
+    feeds_list: &mut FeedsList,
+) -> Option<()> {
+    let content = fs::read_to_string(path).ok()?;
+    visited.insert(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+
+    let mut group = include_group;
+    for (i, line) in content.lines().map(str::trim).enumerate() {
+        if let Some(label) = parse_group_header(line) {
+            group = Some(label);
+            continue;
+        }
+        let Some(include) = line.strip_prefix("@include") else {
+            match parse_feed_line(line) {
+                Some(Ok(url)) => feeds_list.push_url(url, group),
+                Some(Err(_)) => feeds_list.invalid_lines.push(InvalidLine {
+                    path: path.to_path_buf(),
+                    line_no: i + 1,
+                    line: line.to_owned(),
+                }),
+                None => {}
+            }
+            continue;
+        };
+        let include = include.trim();
+        if include.is_empty() {
+            (feeds_list.skipped_includes).push(SkippedInclude::MissingPath(path.to_path_buf()));
+            continue;
+        }
+
+        let include_path = path.parent().unwrap_or(Path::new("")).join(include);
+        let canonical_include_path = include_path.canonicalize().unwrap_or(include_path.clone());
+        if visited.contains(&canonical_include_path) {
+            (feeds_list.skipped_includes).push(SkippedInclude::Cyclic(include_path));
+            continue;
+        }
+        if read_feeds_file_into(&include_path, group, visited, feeds_list).is_none() {
+            (feeds_list.skipped_includes).push(SkippedInclude::Unreadable(include_path));
+        }
+    }
+    Some(())
+}
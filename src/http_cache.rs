@@ -0,0 +1,84 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+
+use crate::utils::stable_hash;
+
+// Format of HTTP dates (RFC 9110), e.g. "Wed, 14 Oct 2026 08:00:00 GMT"
+const HTTP_DATE_FMT: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+// A previously fetched response along with the validators needed to make conditional requests for it
+#[derive(Serialize, Deserialize)]
+pub(crate) struct CachedResponse {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+    // When the response was fetched (as an HTTP date)
+    pub fetched_at: String,
+    #[serde(skip)]
+    pub body: Vec<u8>,
+}
+
+impl CachedResponse {
+    // Value for `If-Modified-Since`, falling back to the time of the fetch for servers that do not send
+    // `Last-Modified` (many of which still honour `If-Modified-Since`)
+    pub(crate) fn if_modified_since(&self) -> &str {
+        self.last_modified.as_deref().unwrap_or(&self.fetched_at)
+    }
+}
+
+// On-disk cache of responses keyed by URL, where each response is stored as a metadata (validators) file
+// and a body file. Without a directory, the cache is disabled (i.e. always misses and stores nothing)
+#[derive(Clone)]
+pub(crate) struct HttpCache {
+    dir: Option<PathBuf>,
+}
+
+impl HttpCache {
+    pub(crate) fn new(dir: Option<PathBuf>) -> Self {
+        Self { dir }
+    }
+
+    fn paths(dir: &Path, url: &str) -> (PathBuf, PathBuf) {
+        let key = format!("{:016x}", stable_hash(url));
+        (
+            dir.join(format!("{key}.json")),
+            dir.join(format!("{key}.body")),
+        )
+    }
+
+    // Missing or malformed entries are treated as cache misses
+    pub(crate) async fn get(&self, url: &str) -> Option<CachedResponse> {
+        let (meta_path, body_path) = Self::paths(self.dir.as_ref()?, url);
+        let meta = fs::read_to_string(meta_path).await.ok()?;
+        let mut cached: CachedResponse = serde_json::from_str(&meta).ok()?;
+        cached.body = fs::read(body_path).await.ok()?;
+        Some(cached)
+    }
+
+    pub(crate) async fn put(
+        &self,
+        url: &str,
+        etag: Option<String>,
+        last_modified: Option<String>,
+        body: &[u8],
+    ) -> io::Result<()> {
+        let Some(ref dir) = self.dir else {
+            return Ok(());
+        };
+        let cached = CachedResponse {
+            etag,
+            last_modified,
+            fetched_at: chrono::Utc::now().format(HTTP_DATE_FMT).to_string(),
+            body: Vec::new(),
+        };
+        let (meta_path, body_path) = Self::paths(dir, url);
+        fs::create_dir_all(dir).await?;
+        // The body is written first, so that validators never refer to a body that was not written
+        fs::write(body_path, body).await?;
+        fs::write(meta_path, serde_json::to_vec(&cached)?).await
+    }
+}
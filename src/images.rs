@@ -0,0 +1,225 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+use ratatui_image::{
+    StatefulImage,
+    picker::{Picker, ProtocolType, cap_parser::QueryStdioOptions},
+    protocol::StatefulProtocol,
+};
+use reqwest::Client;
+use tokio::sync::Notify;
+use url::Url;
+
+// How long to wait for the terminal to answer the graphics queries. Terminals answer right away, so this
+// only holds up the start of those that do not answer at all (e.g. tmux without passthrough)
+const QUERY_TIMEOUT: Duration = Duration::from_millis(250);
+
+// Images embedded in the content of items (e.g. `<img>`) are kept by `try_parse_html` as lines of their
+// own, of the form `<marker>src<marker>alt<marker>` where the marker is the object replacement character
+pub(crate) const IMAGE_MARKER: char = '\u{FFFC}';
+
+pub(crate) fn image_line(src: &str, alt: &str) -> String {
+    format!("{IMAGE_MARKER}{src}{IMAGE_MARKER}{alt}{IMAGE_MARKER}")
+}
+
+// Source and alt text (which may be empty) of an image line
+pub(crate) fn parse_image_line(line: &str) -> Option<(&str, &str)> {
+    line.strip_prefix(IMAGE_MARKER)?
+        .strip_suffix(IMAGE_MARKER)?
+        .split_once(IMAGE_MARKER)
+}
+
+// Split the images of a line (e.g. of a paragraph with an inline image) onto lines of their own, dropping
+// the blank text left around them
+pub(crate) fn split_image_lines(line: &str) -> Vec<String> {
+    if !line.contains(IMAGE_MARKER) {
+        return vec![line.to_owned()];
+    }
+    let mut lines = Vec::new();
+    let mut rest = line;
+    while let Some(start) = rest.find(IMAGE_MARKER) {
+        let image = rest[start..]
+            .match_indices(IMAGE_MARKER)
+            .nth(2)
+            .map(|(end, _)| &rest[start..start + end + IMAGE_MARKER.len_utf8()]);
+        let Some(image) = image.filter(|image| parse_image_line(image).is_some()) else {
+            break;
+        };
+        let (mut before, mut after) = (&rest[..start], &rest[start + image.len()..]);
+        // The brackets of a link around just an image (e.g. "[<image>][1]"), which would otherwise be left
+        // on lines of their own, whereas the footnote reference is kept
+        if let (Some(link_before), Some(link_after)) =
+            (before.strip_suffix('['), after.strip_prefix(']'))
+        {
+            (before, after) = (link_before, link_after);
+        }
+        if !before.trim().is_empty() {
+            lines.push(before.to_owned());
+        }
+        lines.push(image.to_owned());
+        rest = after;
+    }
+    if !rest.trim().is_empty() {
+        lines.push(rest.to_owned());
+    }
+    lines
+}
+
+// URL of an image to download, where relative sources (e.g. "/img/a.png") are relative to the item's URL.
+// `None` for images that cannot be downloaded (e.g. inlined as `data:` URLs)
+pub(crate) fn resolve_src(src: &str, item_url: Option<&str>) -> Option<String> {
+    let url = match item_url.and_then(|item_url| Url::parse(item_url).ok()) {
+        Some(item_url) => item_url.join(src),
+        None => Url::parse(src),
+    }
+    .ok()?;
+    matches!(url.scheme(), "http" | "https").then(|| url.to_string())
+}
+
+// Text shown in place of an image, e.g. when images are disabled or while the image is downloaded
+pub(crate) fn alt_text(alt: &str) -> String {
+    match alt {
+        "" => "[image]".to_string(),
+        alt => format!("[image: {}]", alt),
+    }
+}
+
+// A line with its image (if any) replaced by its alt text, e.g. for commands that only take text
+pub(crate) fn without_image(line: &str) -> Cow<'_, str> {
+    match parse_image_line(line) {
+        Some((_, alt)) => Cow::Owned(alt_text(alt)),
+        None => Cow::Borrowed(line),
+    }
+}
+
+enum CachedImage {
+    Downloading,
+    Downloaded {
+        protocol: Box<StatefulProtocol>,
+        // In pixels
+        size: (u32, u32),
+    },
+    Failed,
+}
+
+// Images downloaded (once) in the background, and kept in memory for as long as the app runs so that
+// scrolling and re-expanding items does not download them again
+#[derive(Clone)]
+pub(crate) struct ImageCache {
+    picker: Picker,
+    http_client: Client,
+    images: Arc<Mutex<HashMap<String, CachedImage>>>,
+    downloaded: Arc<Notify>,
+}
+
+impl ImageCache {
+    pub(crate) fn new(picker: Picker, http_client: Client, downloaded: Arc<Notify>) -> Self {
+        Self {
+            picker,
+            http_client,
+            images: Arc::default(),
+            downloaded,
+        }
+    }
+
+    // Query the terminal for a graphics protocol (e.g. Kitty, iTerm2 or sixel) to show images with,
+    // returning `None` if there is none. Half blocks are not used, as images are too coarse to make
+    // out in them. Must be called in raw mode and before any terminal events are read
+    pub(crate) fn query_picker() -> Option<Picker> {
+        Picker::from_query_stdio_with_options(QueryStdioOptions {
+            timeout: QUERY_TIMEOUT,
+            ..QueryStdioOptions::default()
+        })
+        .ok()
+        .filter(|picker| picker.protocol_type() != ProtocolType::Halfblocks)
+    }
+
+    // Download and decode an image in the background, unless it was already (tried to be) downloaded
+    pub(crate) fn request(&self, url: &str) {
+        {
+            let mut images = self.images.lock().unwrap();
+            if images.contains_key(url) {
+                return;
+            }
+            images.insert(url.to_owned(), CachedImage::Downloading);
+        }
+
+        let url = url.to_owned();
+        let cache = self.clone();
+        tokio::spawn(async move {
+            let downloaded = async {
+                let bytes = cache
+                    .http_client
+                    .get(&url)
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .bytes()
+                    .await?;
+                let picker = cache.picker.clone();
+                // Decoding (and encoding for the terminal) is CPU-bound, and would otherwise hold up other
+                // tasks (e.g. feed fetches)
+                tokio::task::spawn_blocking(move || {
+                    let image = image::load_from_memory(&bytes)?;
+                    let size = (image.width(), image.height());
+                    let protocol = Box::new(picker.new_resize_protocol(image));
+                    Ok::<_, Box<dyn std::error::Error + Send + Sync>>(CachedImage::Downloaded {
+                        protocol,
+                        size,
+                    })
+                })
+                .await?
+            }
+            .await;
+
+            // A failed image is shown as its alt text, like any image that is not downloaded
+            let cached = downloaded.unwrap_or(CachedImage::Failed);
+            let is_downloaded = matches!(cached, CachedImage::Downloaded { .. });
+            cache.images.lock().unwrap().insert(url, cached);
+            if is_downloaded {
+                cache.downloaded.notify_one();
+            }
+        });
+    }
+
+    // Number of downloaded images, which changes whenever another image can be shown
+    pub(crate) fn downloaded_count(&self) -> usize {
+        self.images
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|image| matches!(image, CachedImage::Downloaded { .. }))
+            .count()
+    }
+
+    // Size (in cells) of a downloaded image at its natural size, scaled down (keeping its aspect ratio) to
+    // fit within `max_width` x `max_height`. `None` if the image is not downloaded (yet)
+    pub(crate) fn fit(&self, url: &str, max_width: u16, max_height: u16) -> Option<(u16, u16)> {
+        let images = self.images.lock().unwrap();
+        let Some(CachedImage::Downloaded { size: (w, h), .. }) = images.get(url) else {
+            return None;
+        };
+        let (font_w, font_h) = self.picker.font_size();
+        let cols = w.div_ceil(font_w.max(1).into()) as f64;
+        let rows = h.div_ceil(font_h.max(1).into()) as f64;
+        let scale = (max_width as f64 / cols)
+            .min(max_height as f64 / rows)
+            .min(1.0);
+        Some((
+            ((cols * scale) as u16).max(1),
+            ((rows * scale) as u16).max(1),
+        ))
+    }
+
+    pub(crate) fn render(&self, url: &str, area: Rect, buf: &mut Buffer) {
+        let mut images = self.images.lock().unwrap();
+        if let Some(CachedImage::Downloaded { protocol, .. }) = images.get_mut(url) {
+            StatefulImage::default().render(area, buf, protocol.as_mut());
+        }
+    }
+}
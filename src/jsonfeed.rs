@@ -0,0 +1,58 @@
+use serde::Deserialize;
+
+// JSON Feed (https://jsonfeed.org/version/1.1), limited to the fields that are shown
+#[derive(Deserialize)]
+pub(crate) struct JsonFeed {
+    // Required by the spec, and used to tell a JSON Feed apart from any other JSON document
+    pub version: String,
+    #[serde(default)]
+    pub title: String,
+    pub home_page_url: Option<String>,
+    #[serde(default)]
+    pub items: Vec<JsonFeedItem>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct JsonFeedItem {
+    // A string as per the spec, although some feeds use numbers
+    #[serde(default)]
+    pub id: serde_json::Value,
+    pub url: Option<String>,
+    pub external_url: Option<String>,
+    pub title: Option<String>,
+    pub content_html: Option<String>,
+    pub content_text: Option<String>,
+    pub summary: Option<String>,
+    pub date_published: Option<String>,
+    pub date_modified: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub authors: Vec<JsonFeedAuthor>,
+    // Deprecated single author of JSON Feed 1.0, superseded by `authors`
+    pub author: Option<JsonFeedAuthor>,
+    #[serde(default)]
+    pub attachments: Vec<JsonFeedAttachment>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct JsonFeedAttachment {
+    pub url: String,
+    pub mime_type: Option<String>,
+    // A number as per the spec, which is only used if it is a whole number
+    #[serde(default)]
+    pub size_in_bytes: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct JsonFeedAuthor {
+    pub name: Option<String>,
+}
+
+impl JsonFeed {
+    pub(crate) fn read_from(bytes: &[u8]) -> Option<Self> {
+        serde_json::from_slice::<Self>(bytes)
+            .ok()
+            .filter(|feed| feed.version.starts_with("https://jsonfeed.org/version/"))
+    }
+}
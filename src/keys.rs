@@ -0,0 +1,370 @@
+use std::{fmt, fs, io, path::Path};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+use crate::event::AppEvent;
+
+// Actions that can be (re)bound in the keys file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Action {
+    ScrollDown,
+    ScrollUp,
+    HalfPageDown,
+    HalfPageUp,
+    PageDown,
+    PageUp,
+    Top,
+    Bottom,
+    Expand,
+    Open,
+    Close,
+    Exit,
+}
+
+impl Action {
+    pub(crate) fn app_event(self) -> AppEvent {
+        match self {
+            Action::ScrollDown => AppEvent::Scroll(1),
+            Action::ScrollUp => AppEvent::Scroll(-1),
+            Action::HalfPageDown => AppEvent::ScrollPage(1),
+            Action::HalfPageUp => AppEvent::ScrollPage(-1),
+            Action::PageDown => AppEvent::ScrollPage(2),
+            Action::PageUp => AppEvent::ScrollPage(-2),
+            Action::Top => AppEvent::Scroll(isize::MIN),
+            Action::Bottom => AppEvent::Scroll(isize::MAX),
+            Action::Expand => AppEvent::Expand,
+            Action::Open => AppEvent::Open,
+            Action::Close => AppEvent::Close,
+            Action::Exit => AppEvent::Exit,
+        }
+    }
+}
+
+// A key along with the modifiers (e.g. Ctrl) that must be held for it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Key {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl Key {
+    const fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        Self { code, modifiers }
+    }
+
+    // Shift is part of the character itself (e.g. `G`) and is thus ignored for characters, as terminals
+    // are inconsistent in reporting it
+    fn normalise(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let modifiers = match code {
+            KeyCode::Char(_) => modifiers - KeyModifiers::SHIFT,
+            _ => modifiers,
+        };
+        Self { code, modifiers }
+    }
+
+    // Parse a key specification such as "j", "G", "enter", "ctrl+d" or "alt+shift+up"
+    fn parse(spec: &str) -> Result<Self, String> {
+        // `+` is both the separator and a key of its own (e.g. "+" or "ctrl++")
+        let (modifiers, key) = match spec.strip_suffix("++") {
+            Some(modifiers) => (Some(modifiers), "+"),
+            None if spec == "+" => (None, "+"),
+            None => match spec.rsplit_once('+') {
+                Some((modifiers, key)) => (Some(modifiers), key),
+                None => (None, spec),
+            },
+        };
+
+        let mut key_modifiers = KeyModifiers::NONE;
+        for modifier in modifiers
+            .into_iter()
+            .flat_map(|modifiers| modifiers.split('+'))
+        {
+            key_modifiers |= match modifier.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => {
+                    return Err(format!(
+                        "unknown modifier `{}` (expected ctrl, alt or shift)",
+                        modifier
+                    ));
+                }
+            };
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_ascii_lowercase().as_str() {
+                "enter" | "return" => KeyCode::Enter,
+                "esc" | "escape" => KeyCode::Esc,
+                "tab" => KeyCode::Tab,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" | "ins" => KeyCode::Insert,
+                "space" => KeyCode::Char(' '),
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "" => return Err("missing key".to_string()),
+                name => match name.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                    Some(n @ 1..=24) => KeyCode::F(n),
+                    _ => return Err(format!("unknown key `{}`", key)),
+                },
+            },
+        };
+        // "shift+g" is the same as "G"
+        let code = match code {
+            KeyCode::Char(c) if key_modifiers.contains(KeyModifiers::SHIFT) => {
+                KeyCode::Char(c.to_ascii_uppercase())
+            }
+            code => code,
+        };
+        Ok(Self::normalise(code, key_modifiers))
+    }
+}
+
+impl From<&KeyEvent> for Key {
+    fn from(key_event: &KeyEvent) -> Self {
+        Self::normalise(key_event.code, key_event.modifiers)
+    }
+}
+
+// Shown in the key hints, e.g. "j", "Enter" or "Ctrl+D"
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (KeyModifiers::CONTROL, "Ctrl+"),
+            (KeyModifiers::ALT, "Alt+"),
+            (KeyModifiers::SHIFT, "Shift+"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}", name)?;
+            }
+        }
+        match self.code {
+            // Letters are upper-cased when combined with modifiers, as in "Ctrl+D"
+            KeyCode::Char(c) if !self.modifiers.is_empty() => {
+                write!(f, "{}", c.to_ascii_uppercase())
+            }
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{}", c),
+            KeyCode::Up => write!(f, "↑"),
+            KeyCode::Down => write!(f, "↓"),
+            KeyCode::Left => write!(f, "←"),
+            KeyCode::Right => write!(f, "→"),
+            KeyCode::Backspace => write!(f, "⌫"),
+            KeyCode::PageDown => write!(f, "PgDn"),
+            KeyCode::PageUp => write!(f, "PgUp"),
+            code => write!(f, "{}", code),
+        }
+    }
+}
+
+// Keys bound to each action, where the first binding of a key wins
+#[derive(Clone, Debug)]
+pub(crate) struct KeyBindings {
+    bindings: Vec<(Key, Action)>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            bindings: DEFAULT_KEYS
+                .iter()
+                .flat_map(|(action, keys)| keys.iter().map(|key| (*key, *action)))
+                .collect(),
+        }
+    }
+}
+
+const DEFAULT_KEYS: [(Action, &[Key]); 12] = [
+    (
+        Action::ScrollDown,
+        &[
+            Key::new(KeyCode::Char('j'), KeyModifiers::NONE),
+            Key::new(KeyCode::Down, KeyModifiers::NONE),
+        ],
+    ),
+    (
+        Action::ScrollUp,
+        &[
+            Key::new(KeyCode::Char('k'), KeyModifiers::NONE),
+            Key::new(KeyCode::Up, KeyModifiers::NONE),
+        ],
+    ),
+    (
+        Action::HalfPageDown,
+        &[Key::new(KeyCode::Char('d'), KeyModifiers::CONTROL)],
+    ),
+    (
+        Action::HalfPageUp,
+        &[Key::new(KeyCode::Char('u'), KeyModifiers::CONTROL)],
+    ),
+    (
+        Action::PageDown,
+        &[Key::new(KeyCode::PageDown, KeyModifiers::NONE)],
+    ),
+    (
+        Action::PageUp,
+        &[Key::new(KeyCode::PageUp, KeyModifiers::NONE)],
+    ),
+    (
+        Action::Top,
+        &[Key::new(KeyCode::Char('g'), KeyModifiers::NONE)],
+    ),
+    (
+        Action::Bottom,
+        &[Key::new(KeyCode::Char('G'), KeyModifiers::NONE)],
+    ),
+    (
+        Action::Expand,
+        &[Key::new(KeyCode::Enter, KeyModifiers::NONE)],
+    ),
+    (
+        Action::Open,
+        &[Key::new(KeyCode::Char('o'), KeyModifiers::NONE)],
+    ),
+    (
+        Action::Close,
+        &[Key::new(KeyCode::Char('q'), KeyModifiers::NONE)],
+    ),
+    // Ctrl+D scrolls down by half a page (as in less and vim), leaving Ctrl+C to exit
+    (
+        Action::Exit,
+        &[Key::new(KeyCode::Char('c'), KeyModifiers::CONTROL)],
+    ),
+];
+
+// A single key or a list of keys, e.g. `scroll_down = "j"` or `scroll_down = ["j", "down"]`
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum KeySpecs {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeysFile {
+    scroll_down: Option<KeySpecs>,
+    scroll_up: Option<KeySpecs>,
+    half_page_down: Option<KeySpecs>,
+    half_page_up: Option<KeySpecs>,
+    page_down: Option<KeySpecs>,
+    page_up: Option<KeySpecs>,
+    top: Option<KeySpecs>,
+    bottom: Option<KeySpecs>,
+    expand: Option<KeySpecs>,
+    open: Option<KeySpecs>,
+    close: Option<KeySpecs>,
+    exit: Option<KeySpecs>,
+}
+
+#[derive(Debug)]
+pub(crate) enum KeysError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    InvalidKey {
+        action: &'static str,
+        spec: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for KeysError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeysError::Io(e) => write!(f, "{}", e),
+            KeysError::Toml(e) => write!(f, "{}", e),
+            KeysError::InvalidKey {
+                action,
+                spec,
+                reason,
+            } => write!(f, "invalid key \"{}\" for `{}`: {}", spec, action, reason),
+        }
+    }
+}
+
+impl std::error::Error for KeysError {}
+
+impl KeyBindings {
+    // Load the bindings of a keys file, where actions that are not in the file keep their default keys.
+    // A missing file is the same as an empty one
+    pub(crate) fn load(path: &Path) -> Result<Self, KeysError> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(KeysError::Io(e)),
+        };
+        let keys_file: KeysFile = toml::from_str(&content).map_err(KeysError::Toml)?;
+
+        let overrides = [
+            ("scroll_down", Action::ScrollDown, keys_file.scroll_down),
+            ("scroll_up", Action::ScrollUp, keys_file.scroll_up),
+            (
+                "half_page_down",
+                Action::HalfPageDown,
+                keys_file.half_page_down,
+            ),
+            ("half_page_up", Action::HalfPageUp, keys_file.half_page_up),
+            ("page_down", Action::PageDown, keys_file.page_down),
+            ("page_up", Action::PageUp, keys_file.page_up),
+            ("top", Action::Top, keys_file.top),
+            ("bottom", Action::Bottom, keys_file.bottom),
+            ("expand", Action::Expand, keys_file.expand),
+            ("open", Action::Open, keys_file.open),
+            ("close", Action::Close, keys_file.close),
+            ("exit", Action::Exit, keys_file.exit),
+        ];
+
+        let mut bindings = Vec::new();
+        for ((name, action, specs), (_, default_keys)) in overrides.into_iter().zip(DEFAULT_KEYS) {
+            let Some(specs) = specs else {
+                bindings.extend(default_keys.iter().map(|key| (*key, action)));
+                continue;
+            };
+            let specs = match specs {
+                KeySpecs::One(spec) => vec![spec],
+                KeySpecs::Many(specs) => specs,
+            };
+            for spec in specs {
+                let key = Key::parse(&spec).map_err(|reason| KeysError::InvalidKey {
+                    action: name,
+                    spec: spec.clone(),
+                    reason,
+                })?;
+                bindings.push((key, action));
+            }
+        }
+        Ok(Self { bindings })
+    }
+
+    // Keys bound to `actions` for the key hints, e.g. "j/↓/k/↑"
+    pub(crate) fn hint(&self, actions: &[Action]) -> String {
+        actions
+            .iter()
+            .flat_map(|action| {
+                self.bindings
+                    .iter()
+                    .filter(move |(_, bound_action)| bound_action == action)
+            })
+            .map(|(key, _)| key.to_string())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    pub(crate) fn action(&self, key_event: &KeyEvent) -> Option<Action> {
+        let key = Key::from(key_event);
+        self.bindings
+            .iter()
+            .find(|(bound_key, _)| *bound_key == key)
+            .map(|(_, action)| *action)
+    }
+}
@@ -1,33 +1,158 @@
+use chrono::{Local, TimeDelta};
 use clap::{Parser, Subcommand};
+use directories::{ProjectDirs, UserDirs};
 use ratatui::Terminal;
+use ratatui::crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use ratatui::crossterm::execute;
 use ratatui::crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
 };
 use ratatui::prelude::CrosstermBackend;
-use std::env::home_dir;
+use ratatui::widgets::Padding;
+use std::env::{self, home_dir};
 use std::error::Error;
 use std::fs::{self};
-use std::io::{Read, Write};
+use std::io;
+use std::io::{IsTerminal, Read, Write};
 use std::panic::{set_hook, take_hook};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process;
 use std::time::Duration;
-use std::{f32, io};
 use url::Url;
 
 mod app;
+mod bookmarks;
+mod config;
 mod debug;
+mod discover;
 mod event;
+mod feeds;
+mod http_cache;
+mod images;
+mod jsonfeed;
+mod keys;
+mod opml;
+mod state;
 mod stream;
 mod utils;
 
 use crate::app::App;
+use crate::config::{Config, SourceLabels, View};
+use crate::debug::PerfLog;
+use crate::discover::{Discovery, FeedLink};
+use crate::images::ImageCache;
+use crate::keys::KeyBindings;
+use crate::utils::{LONG_TIMESTAMP_FMT, TRACKING_PARAMS, Theme, ThemePreset};
 
-fn default_feeds_file() -> PathBuf {
+// Resolve an app directory from (in order) the home directory, an XDG base directory variable, and the
+// platform's own convention (e.g. known folders on Windows)
+fn resolve_dir(
+    home_rel_path: &str,
+    xdg_var: &str,
+    platform_dir: fn(&ProjectDirs) -> &Path,
+) -> Option<PathBuf> {
     home_dir()
-        .map(|home_dir| home_dir.join(".config/rssterm/feeds.txt"))
-        // Fallback to relative path if HOME is not set
-        .unwrap_or_else(|| PathBuf::from("feeds.txt"))
+        .filter(|home_dir| !home_dir.as_os_str().is_empty())
+        .map(|home_dir| home_dir.join(home_rel_path))
+        .or_else(|| {
+            env::var_os(xdg_var)
+                .map(PathBuf::from)
+                // Relative XDG paths are invalid as per the spec, and should be ignored
+                .filter(|xdg_dir| xdg_dir.is_absolute())
+                .map(|xdg_dir| xdg_dir.join(env!("CARGO_PKG_NAME")))
+        })
+        .or_else(|| {
+            ProjectDirs::from("", "", env!("CARGO_PKG_NAME"))
+                .map(|dirs| platform_dir(&dirs).to_path_buf())
+        })
+}
+
+// Directory of all config and state files. Fallback to the working directory if no directory could be
+// resolved (e.g. HOME is not set)
+fn config_dir() -> PathBuf {
+    resolve_dir(
+        ".config/rssterm",
+        "XDG_CONFIG_HOME",
+        ProjectDirs::config_dir,
+    )
+    .unwrap_or_default()
+}
+
+// Directory of all cache files. Fallback to a directory within the config directory, rather than the
+// config directory itself, so that cache files never collide with config files
+fn cache_dir() -> PathBuf {
+    resolve_dir(".cache/rssterm", "XDG_CACHE_HOME", ProjectDirs::cache_dir)
+        .unwrap_or_else(|| config_dir().join("cache"))
+}
+
+fn default_feeds_file() -> PathBuf {
+    config_dir().join("feeds.txt")
+}
+
+fn default_feeds_url_cache_file() -> PathBuf {
+    cache_dir().join("feeds.txt")
+}
+
+fn default_keys_file() -> PathBuf {
+    config_dir().join("keys.toml")
+}
+
+fn default_theme_file() -> PathBuf {
+    config_dir().join("theme.toml")
+}
+
+fn default_state_file() -> PathBuf {
+    config_dir().join("state.json")
+}
+
+fn default_bookmarks_file() -> PathBuf {
+    config_dir().join("bookmarks.txt")
+}
+
+// The user's downloads directory (e.g. ~/Downloads), or otherwise a directory within the config directory
+fn default_downloads_dir() -> PathBuf {
+    UserDirs::new()
+        .and_then(|dirs| dirs.download_dir().map(Path::to_path_buf))
+        .unwrap_or_else(|| config_dir().join("downloads"))
+}
+
+// Parse padding given as CSS-like shorthand, i.e. "ALL", "VERTICAL,HORIZONTAL" or "TOP,RIGHT,BOTTOM,LEFT"
+fn parse_padding(value: &str) -> Result<Padding, String> {
+    let sides = value
+        .split(',')
+        .map(|side| side.trim().parse::<u16>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    match sides[..] {
+        [all] => Ok(Padding::uniform(all)),
+        [vertical, horizontal] => Ok(Padding::symmetric(horizontal, vertical)),
+        [top, right, bottom, left] => Ok(Padding::new(left, right, top, bottom)),
+        _ => Err("expected 1, 2 or 4 comma-separated values".to_string()),
+    }
+}
+
+// Check that a proxy URL has a scheme that requests can be proxied with
+fn parse_proxy(value: &str) -> Result<Url, String> {
+    let url = Url::parse(value).map_err(|e| e.to_string())?;
+    match url.scheme() {
+        "http" | "https" => Ok(url),
+        scheme => Err(format!(
+            "unsupported proxy scheme `{}` (expected http or https)",
+            scheme
+        )),
+    }
+}
+
+// Check that a strftime-like format (e.g. "%H:%M") can be formatted by chrono, which would otherwise
+// panic when it is rendered
+fn parse_time_format(value: &str) -> Result<String, String> {
+    use std::fmt::Write;
+    let mut formatted = String::new();
+    write!(formatted, "{}", Local::now().format(value)).map_err(|_| {
+        "invalid format (see https://docs.rs/chrono/latest/chrono/format/strftime for the specifiers)"
+            .to_string()
+    })?;
+    Ok(value.to_owned())
 }
 
 #[derive(Parser)]
@@ -36,6 +161,67 @@ fn default_feeds_file() -> PathBuf {
 struct Cli {
     #[arg(long = "feeds", env = "RSSTERM_FEEDS", default_value = default_feeds_file().into_os_string())]
     feeds_file: PathBuf,
+    #[arg(
+        long,
+        env = "RSSTERM_FEEDS_URL",
+        value_parser = Url::parse,
+        help = "URL of a remote feeds list to use instead of the feeds file (cached locally for offline use)"
+    )]
+    feeds_url: Option<Url>,
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Always download feeds in full, neither using nor updating the cache of previously fetched feeds"
+    )]
+    no_cache: bool,
+    #[arg(
+        long,
+        value_name = "SECS",
+        default_value_t = 10,
+        help = "Timeout of each request, after which the feed is shown as failed (use 0 for no timeout)"
+    )]
+    timeout: u64,
+    #[arg(
+        long,
+        value_name = "URL",
+        value_parser = parse_proxy,
+        help = "Proxy to send every request through (e.g. http://proxy.example.com:3128), instead of HTTP_PROXY/HTTPS_PROXY (hosts in NO_PROXY are still not proxied)"
+    )]
+    proxy: Option<Url>,
+    #[arg(
+        long,
+        default_value_t = 2,
+        help = "Maximum number of retries (with exponential backoff) of feed requests that fail with a connection or server (5xx) error"
+    )]
+    max_retries: u32,
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 8,
+        value_parser = clap::value_parser!(u16).range(1..),
+        help = "Maximum number of feeds fetched at once"
+    )]
+    max_concurrency: u16,
+    #[arg(
+        long = "refresh",
+        value_name = "SECS",
+        help = "Re-fetch the feeds every SECS seconds in the background (use 0 to disable, the default)"
+    )]
+    refresh_secs: Option<u64>,
+    #[arg(
+        long = "keys",
+        env = "RSSTERM_KEYS",
+        default_value = default_keys_file().into_os_string(),
+        help = "TOML file mapping actions (scroll_down, scroll_up, half_page_down, half_page_up, page_down, page_up, top, bottom, expand, open, close, exit) to keys, e.g. `scroll_down = [\"h\", \"down\"]`"
+    )]
+    keys_file: PathBuf,
+    #[arg(
+        long,
+        value_name = "DIR",
+        default_value = default_downloads_dir().into_os_string(),
+        help = "Directory that enclosures (e.g. podcast episodes) are downloaded to with `w`"
+    )]
+    downloads_dir: PathBuf,
     #[arg(
         long,
         default_value_t = 120.0,
@@ -44,6 +230,214 @@ struct Cli {
     fps: f32,
     #[arg(long, default_value_t = false)]
     show_fps: bool,
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write the timings of every frame drawn to PATH as CSV, for profiling"
+    )]
+    perf_log: Option<PathBuf>,
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Hide scrollbars when all content fits on screen"
+    )]
+    auto_hide_scrollbar: bool,
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Wrap around at the ends of the list, scrolling down from the last item to the first and up from the first to the last"
+    )]
+    wrap_scroll: bool,
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Capture the mouse, so that clicking an item selects it and clicking it again opens it (text can then only be selected with the terminal's override, e.g. Shift+drag)"
+    )]
+    mouse: bool,
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Ask for confirmation before exiting with the exit key (Ctrl+C by default), as closing the list with q or Esc does"
+    )]
+    confirm_quit: bool,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = View::default(),
+        help = "View shown on launch"
+    )]
+    default_view: View,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ThemePreset::default(),
+        help = "Color theme, whose colors can be overridden in theme.toml of the config directory (e.g. `title = \"#e8e9f0\"`)"
+    )]
+    theme: ThemePreset,
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Render without colors, using only the terminal's default colors (also enabled by a non-empty NO_COLOR)"
+    )]
+    no_color: bool,
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Show the alt text of images in the expanded view instead of the images (which are only shown in terminals with a graphics protocol, e.g. Kitty, iTerm2 or sixel)"
+    )]
+    no_images: bool,
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Do not make the URLs of the expanded item clickable with OSC 8 hyperlinks (for terminals that show the escape sequences as text)"
+    )]
+    no_hyperlinks: bool,
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Follow `rel=\"next\"` links of paged (RFC 5005) feeds"
+    )]
+    follow_paging: bool,
+    #[arg(
+        long,
+        default_value_t = 5,
+        value_parser = clap::value_parser!(u16).range(1..),
+        help = "Maximum number of pages fetched per feed when following paging"
+    )]
+    max_pages: u16,
+    #[arg(
+        long,
+        visible_alias = "limit",
+        value_name = "N",
+        default_value_t = 100,
+        help = "Maximum number of items kept per feed, newest first (use 0 for no limit)"
+    )]
+    max_items_per_feed: usize,
+    #[arg(
+        long,
+        value_name = "N",
+        value_parser = clap::value_parser!(u32).range(1..),
+        help = "Maximum number of items kept across all feeds, newest first"
+    )]
+    max_items_total: Option<u32>,
+    #[arg(
+        long,
+        value_enum,
+        value_name = "STYLE",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "runs",
+        help = "Label items with the feed they come from, above each run of items from a feed (runs) or after every title (tags)"
+    )]
+    show_source: Option<SourceLabels>,
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Show absolute instead of relative publish dates in the list (toggle with `d`)"
+    )]
+    absolute_dates: bool,
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Hide item URLs in the list for denser rows (toggle with `L`)"
+    )]
+    hide_urls: bool,
+    #[arg(
+        long,
+        value_name = "HOURS",
+        help = "Mark items published within the last HOURS as new, instead of items that arrived since the last quit"
+    )]
+    new_within_hours: Option<u32>,
+    #[arg(
+        long,
+        value_name = "FORMAT",
+        default_value = LONG_TIMESTAMP_FMT,
+        value_parser = parse_time_format,
+        help = "Format (strftime-like, e.g. \"%Y-%m-%d %H:%M\") of the clock and of the dates in the expanded view"
+    )]
+    time_format: String,
+    #[arg(
+        long,
+        default_value_t = 3,
+        help = "Maximum number of authors shown for an item before \"et al.\" (use 0 for no limit)"
+    )]
+    max_authors: usize,
+    #[arg(
+        long,
+        default_value = "1,2",
+        value_parser = parse_padding,
+        help = "Padding around the content of expanded items, as \"ALL\", \"VERTICAL,HORIZONTAL\" or \"TOP,RIGHT,BOTTOM,LEFT\""
+    )]
+    content_padding: Padding,
+    #[arg(
+        long,
+        value_name = "COLS",
+        value_parser = clap::value_parser!(u16).range(1..),
+        help = "Maximum width of the content of expanded items, centered on wider terminals for readability (full width by default)"
+    )]
+    content_width: Option<u16>,
+    #[arg(
+        long,
+        default_value_t = 8,
+        help = "Minimum width for wrapping text, below which text is truncated to a single line instead (use 0 to always wrap)"
+    )]
+    min_wrap_width: usize,
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Preserve the indentation of wrapped lines in item content (e.g. code or ASCII art)"
+    )]
+    preserve_indent: bool,
+    #[arg(
+        long = "strip-param",
+        value_name = "NAME",
+        value_delimiter = ',',
+        help = "Query parameter to strip from URLs before opening or copying them, in addition to tracking parameters such as utm_* (a trailing * matches any suffix, repeatable)"
+    )]
+    strip_params: Vec<String>,
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Open and copy URLs as they are, without stripping tracking parameters"
+    )]
+    keep_tracking_params: bool,
+    #[arg(
+        long,
+        value_name = "TEMPLATE",
+        env = "RSSTERM_OPEN_CMD",
+        help = "Command to open item URLs with (via `sh -c`) instead of the default browser, where {url} is replaced by the shell-quoted URL (so it must not be quoted in the command), e.g. \"firefox --new-tab {url}\""
+    )]
+    open_cmd: Option<String>,
+    #[arg(
+        long,
+        env = "RSSTERM_TRANSLATE_CMD",
+        help = "Command to translate items with (via `sh -c`), receiving the text on stdin and writing the translation to stdout"
+    )]
+    translate_cmd: Option<String>,
+    #[arg(
+        long,
+        env = "RSSTERM_SUMMARIZE_CMD",
+        help = "Command to summarize items with (via `sh -c`), receiving the text on stdin and writing the summary to stdout"
+    )]
+    summarize_cmd: Option<String>,
+    #[arg(
+        long,
+        env = "RSSTERM_ON_NEW_ITEM",
+        help = "Command to run (via `sh -c`) for each new item found by a refresh, with item fields passed as RSSTERM_ITEM_* env vars"
+    )]
+    on_new_item: Option<String>,
+    #[arg(
+        long,
+        env = "RSSTERM_SEND_CMD",
+        help = "Command to send items to with x (via `sh -c`), e.g. a read-it-later CLI, receiving the item's URL on stdin"
+    )]
+    send_cmd: Option<String>,
+    #[arg(
+        long,
+        default_value_t = false,
+        help = "Send the plain-text content of items to the send command instead of their URL"
+    )]
+    send_content: bool,
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -52,11 +446,152 @@ struct Cli {
 enum Commands {
     #[command(about = "Add a new RSS/Atom feed")]
     Add {
-        #[arg(value_parser=Url::parse, help="URL of the RSS/Atom feed (e.g. https://hnrss.org/frontpage)")]
+        #[arg(value_parser=Url::parse, help="URL of the RSS/Atom feed (e.g. https://hnrss.org/frontpage), or of a webpage to add the feed of")]
         url: Url,
+        #[arg(
+            long,
+            help = "Add the URL as is, without checking whether it is a webpage to add the feed of instead"
+        )]
+        no_discover: bool,
+        #[arg(
+            long,
+            help = "Add the URL even if it does not look like a feed (e.g. as its server is down for now)"
+        )]
+        force: bool,
     },
     #[command(about = "Path to feeds file")]
     Feeds,
+    #[command(about = "List the bookmarked items (bookmark an item with b)")]
+    Bookmarks,
+    #[command(about = "List the configured feeds")]
+    List,
+    #[command(about = "Import feeds from an OPML file (e.g. exported from another reader)")]
+    Import {
+        #[arg(help = "Path to the OPML file")]
+        path: PathBuf,
+    },
+    #[command(about = "Remove a feed")]
+    Remove {
+        #[arg(help = "URL of the feed, or its number as shown by `rssterm list`")]
+        target: String,
+    },
+}
+
+// The feed to add for `url`, which is checked to be a feed (printing its title) unless `--force` is given.
+// Webpages are replaced by the feed they advertise (chosen from a prompt if they advertise several),
+// unless `--no-discover` is given. Exits if there is no feed to add
+async fn check_feed_url(
+    url: Url,
+    no_discover: bool,
+    force: bool,
+    timeout: u64,
+    proxy: Option<&Url>,
+) -> Url {
+    let mut http_client = reqwest::Client::builder().user_agent(app::HTTP_USER_AGENT);
+    if timeout > 0 {
+        http_client = http_client.timeout(Duration::from_secs(timeout));
+    }
+    if let Some(proxy) = proxy {
+        let proxy = reqwest::Proxy::all(proxy.as_str())
+            .expect("Proxy is validated when parsing arguments")
+            .no_proxy(reqwest::NoProxy::from_env());
+        http_client = http_client.proxy(proxy);
+    }
+    let http_client = http_client.build().expect("HTTP client is valid");
+
+    let checked = match no_discover {
+        true => discover::feed_title(&http_client, &url).await,
+        false => match discover::discover(&http_client, &url).await {
+            Ok(Discovery::Feed(title)) => Ok(title),
+            Ok(Discovery::Webpage(feed_links)) => {
+                let Some(feed_url) = choose_feed_link(&url, feed_links) else {
+                    let error = "a webpage without any feeds".into();
+                    return refuse_or_force(url, error, force);
+                };
+                return match discover::feed_title(&http_client, &feed_url).await {
+                    Ok(title) => {
+                        println!("Found feed: {}", title);
+                        feed_url
+                    }
+                    Err(e) => refuse_or_force(feed_url, e, force),
+                };
+            }
+            Err(e) => Err(e),
+        },
+    };
+    match checked {
+        Ok(title) => {
+            println!("Found feed: {}", title);
+            url
+        }
+        Err(e) => refuse_or_force(url, e, force),
+    }
+}
+
+// A URL that is not a feed is only added with `--force`, e.g. as its server may just be down for now
+fn refuse_or_force(url: Url, error: Box<dyn Error + Send + Sync>, force: bool) -> Url {
+    if force {
+        eprintln!(
+            "Adding {} anyway, although it could not be checked to be a feed: {}",
+            url, error
+        );
+        return url;
+    }
+    eprintln!(
+        "Not adding {}, as it could not be checked to be a feed: {}\nAdd it anyway with `rssterm add --force {}`",
+        url, error, url
+    );
+    process::exit(1);
+}
+
+// The feed of a webpage to add, prompting for which one if it advertises several (exiting if none is chosen,
+// or there is no one to ask). `None` if it advertises none
+fn choose_feed_link(url: &Url, mut feed_links: Vec<FeedLink>) -> Option<Url> {
+    match feed_links.len() {
+        0 => return None,
+        1 => {
+            println!("{} is a webpage, adding its feed instead", url);
+            return Some(feed_links.remove(0).url);
+        }
+        _ => {}
+    }
+    let is_interactive = io::stdin().is_terminal();
+    match is_interactive {
+        true => println!("{} is a webpage with several feeds:", url),
+        false => eprintln!(
+            "{} is a webpage with several feeds, add one of them instead:",
+            url
+        ),
+    }
+    for (i, feed_link) in feed_links.iter().enumerate() {
+        let line = match &feed_link.title {
+            Some(title) => format!("{:>3}. {}\n     {}", i + 1, title, feed_link.url),
+            None => format!("{:>3}. {}", i + 1, feed_link.url),
+        };
+        match is_interactive {
+            true => println!("{}", line),
+            false => eprintln!("{}", line),
+        }
+    }
+    if !is_interactive {
+        process::exit(1);
+    }
+
+    print!("Add which feed? [1-{}] ", feed_links.len());
+    let mut answer = String::new();
+    let chosen = io::stdout()
+        .flush()
+        .and_then(|_| io::stdin().read_line(&mut answer))
+        .ok()
+        .and_then(|_| answer.trim().parse::<usize>().ok())
+        .filter(|i| (1..=feed_links.len()).contains(i));
+    match chosen {
+        Some(i) => Some(feed_links.swap_remove(i - 1).url),
+        None => {
+            eprintln!("No feed chosen, nothing added");
+            process::exit(1);
+        }
+    }
 }
 
 #[tokio::main]
@@ -68,19 +603,148 @@ async fn main() -> Result<(), Box<dyn Error>> {
             println!("{}", args.feeds_file.display());
             return Ok(());
         }
-        Some(Commands::Add { url }) => {
+        Some(Commands::Bookmarks) => {
+            let bookmarks_file = default_bookmarks_file();
+            let bookmarks = match bookmarks::read_bookmarks(&bookmarks_file) {
+                Ok(bookmarks) => bookmarks,
+                Err(e) => {
+                    eprintln!("Failed to read {}: {}", bookmarks_file.display(), e);
+                    process::exit(1);
+                }
+            };
+            if bookmarks.is_empty() {
+                println!("No bookmarks yet, bookmark an item with b");
+            }
+            for (i, bookmark) in bookmarks.iter().enumerate() {
+                match bookmark.title.as_str() {
+                    "" => println!("{:>3}. {}", i + 1, bookmark.url),
+                    title => println!("{:>3}. {}\n     {}", i + 1, title, bookmark.url),
+                }
+            }
+            return Ok(());
+        }
+        Some(Commands::List) => {
+            let Some(feeds_list) = feeds::read_feeds_file(&args.feeds_file) else {
+                println!(
+                    "No feeds file found at {}, add a feed with `rssterm add <url>`",
+                    args.feeds_file.display()
+                );
+                return Ok(());
+            };
+            // Malformed lines and skipped includes are reported, but do not fail the command as the
+            // remaining feeds are still usable
+            for invalid_line in &feeds_list.invalid_lines {
+                eprintln!(
+                    "{}:{}: not a valid URL: {}",
+                    invalid_line.path.display(),
+                    invalid_line.line_no,
+                    invalid_line.line
+                );
+            }
+            for skipped_include in &feeds_list.skipped_includes {
+                eprintln!("Skipping {}", skipped_include);
+            }
+            if feeds_list.urls.is_empty() {
+                println!("No feeds yet, add a feed with `rssterm add <url>`");
+            }
+            for (i, url) in feeds_list.urls.iter().enumerate() {
+                println!("{:>3}. {}", i + 1, url);
+            }
+            return Ok(());
+        }
+        Some(Commands::Import { path }) => {
+            let opml_urls = fs::read_to_string(&path)
+                .map_err(|e| e.to_string())
+                .and_then(|opml| opml::import(&opml));
+            let opml_urls = match opml_urls {
+                Ok(opml_urls) => opml_urls,
+                Err(e) => {
+                    eprintln!("Failed to import {}: {}", path.display(), e);
+                    process::exit(1);
+                }
+            };
+
+            let mut feeds_file = fs::OpenOptions::new()
+                .read(true)
+                .append(true)
+                .create(true)
+                .open(args.feeds_file.clone())?;
+            let mut feed_urls = String::new();
+            feeds_file.read_to_string(&mut feed_urls)?;
+
+            let (mut added, mut skipped, mut invalid) = (0, 0, 0);
+            for opml_url in opml_urls {
+                let Ok(url) = Url::parse(&opml_url) else {
+                    eprintln!("Skipping invalid feed URL: {}", opml_url);
+                    invalid += 1;
+                    continue;
+                };
+                // Feeds listed more than once in the OPML are skipped as well, as they are appended to
+                // `feed_urls` once added
+                if feeds::has_feed_url(&feed_urls, url.as_str()) {
+                    skipped += 1;
+                    continue;
+                }
+                let line = format!("\n{}", url);
+                feeds_file.write_all(line.as_bytes())?;
+                feed_urls.push_str(&line);
+                added += 1;
+            }
+            println!(
+                "Imported {} feeds ({} already present, {} invalid)",
+                added, skipped, invalid
+            );
+            return Ok(());
+        }
+        Some(Commands::Remove { target }) => {
+            let content = fs::read_to_string(&args.feeds_file).unwrap_or_default();
+            // Numbers refer to the feeds as listed (i.e. including feeds of included files), whereas
+            // anything else is taken as a URL
+            let url = match target.parse::<usize>() {
+                Ok(index) => feeds::read_feeds_file(&args.feeds_file)
+                    .and_then(|feeds_list| feeds_list.urls.into_iter().nth(index.wrapping_sub(1))),
+                Err(_) => Url::parse(&target).ok().map(String::from),
+            };
+            let Some(url) = url else {
+                eprintln!("No feed matches {target}, see `rssterm list` for the configured feeds");
+                process::exit(1);
+            };
+            let Some(new_content) = feeds::remove_feed_url(&content, &url) else {
+                eprintln!(
+                    "{url} is not in {} (it may be from an included file)",
+                    args.feeds_file.display()
+                );
+                process::exit(1);
+            };
+            fs::write(&args.feeds_file, new_content)?;
+            println!("Removed feed: {}", url);
+            return Ok(());
+        }
+        Some(Commands::Add {
+            url,
+            no_discover,
+            force,
+        }) => {
             let mut feeds_file = fs::OpenOptions::new()
                 .read(true)
                 .append(true)
                 .open(args.feeds_file.clone())?;
             let mut feed_urls = String::new();
             feeds_file.read_to_string(&mut feed_urls)?;
-            if feed_urls.lines().any(|line| line.trim() == url.as_str()) {
+            // Checked before and after the URL is, as it may be replaced by the feed of its webpage
+            let is_already_there = |url: &Url| feeds::has_feed_url(&feed_urls, url.as_str());
+            if is_already_there(&url) {
+                eprintln!("{url} is already there!");
+                return Ok(());
+            }
+            let url =
+                check_feed_url(url, no_discover, force, args.timeout, args.proxy.as_ref()).await;
+            if is_already_there(&url) {
                 eprintln!("{url} is already there!");
                 return Ok(());
             }
             // Add a new line
-            feeds_file.write(format!("\n{}", url).as_bytes())?;
+            feeds_file.write_all(format!("\n{}", url).as_bytes())?;
             println!("Added feed: {}", url);
             return Ok(());
         }
@@ -88,32 +752,143 @@ async fn main() -> Result<(), Box<dyn Error>> {
     }
 
     let tick_rate = if args.fps == 0.0 {
-        Duration::from_secs_f32(f32::EPSILON)
+        None
     } else {
-        Duration::from_secs_f32(1.0 / args.fps)
+        Some(Duration::from_secs_f32(1.0 / args.fps))
+    };
+
+    let keys = match KeyBindings::load(&args.keys_file) {
+        Ok(keys) => keys,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", args.keys_file.display(), e);
+            process::exit(1);
+        }
     };
 
-    enable_raw_mode()?;
-    execute!(io::stdout(), EnterAlternateScreen)?;
+    let perf_log = args.perf_log.as_deref().map(|path| {
+        PerfLog::create(path).unwrap_or_else(|e| {
+            eprintln!("Failed to create {}: {}", path.display(), e);
+            process::exit(1);
+        })
+    });
+
+    let theme_file = default_theme_file();
+    let theme = match Theme::load(&theme_file, args.theme) {
+        Ok(theme) => theme,
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", theme_file.display(), e);
+            process::exit(1);
+        }
+    };
+
+    let config = Config {
+        feeds_file: args.feeds_file,
+        feeds_url: args.feeds_url.map(String::from),
+        feeds_url_cache_file: default_feeds_url_cache_file(),
+        http_cache_dir: (!args.no_cache).then(|| cache_dir().join("feeds")),
+        state_file: default_state_file(),
+        bookmarks_file: default_bookmarks_file(),
+        downloads_dir: args.downloads_dir,
+        tick_rate,
+        show_fps: args.show_fps,
+        auto_hide_scrollbar: args.auto_hide_scrollbar,
+        wrap_scroll: args.wrap_scroll,
+        confirm_quit: args.confirm_quit,
+        default_view: args.default_view,
+        theme,
+        show_images: !args.no_images,
+        // https://no-color.org: any non-empty value disables colors
+        no_color: args.no_color || env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()),
+        hyperlinks: !args.no_hyperlinks,
+        timeout: (args.timeout > 0).then(|| Duration::from_secs(args.timeout)),
+        proxy: args.proxy.map(String::from),
+        max_retries: args.max_retries,
+        max_concurrency: args.max_concurrency as usize,
+        refresh_interval: args
+            .refresh_secs
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs),
+        keys,
+        follow_paging: args.follow_paging,
+        max_pages: args.max_pages as usize,
+        max_items_per_feed: Some(args.max_items_per_feed).filter(|max| *max > 0),
+        max_items_total: args.max_items_total.map(|max| max as usize),
+        show_source: args.show_source,
+        absolute_dates: args.absolute_dates,
+        hide_urls: args.hide_urls,
+        new_within: args
+            .new_within_hours
+            .map(|hours| TimeDelta::hours(hours.into())),
+        time_format: args.time_format,
+        content_padding: args.content_padding,
+        content_width: args.content_width,
+        max_authors: args.max_authors,
+        min_wrap_width: args.min_wrap_width,
+        preserve_indent: args.preserve_indent,
+        strip_params: match args.keep_tracking_params {
+            true => args.strip_params,
+            false => (TRACKING_PARAMS.iter().map(|param| param.to_string()))
+                .chain(args.strip_params)
+                .collect(),
+        },
+        open_cmd: args.open_cmd,
+        translate_cmd: args.translate_cmd,
+        summarize_cmd: args.summarize_cmd,
+        send_cmd: args.send_cmd,
+        send_content: args.send_content,
+        on_new_item_cmd: args.on_new_item,
+    };
+
+    // Refuse early with an actionable message, as the TUI reads keys from stdin and draws to stdout, which
+    // pipes and redirects cannot provide
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        eprintln!(
+            "rssterm requires an interactive terminal (stdin and stdout must not be piped or redirected)"
+        );
+        process::exit(1);
+    }
+    if let Err(e) = enable_raw_mode() {
+        eprintln!("rssterm requires an interactive terminal (failed to enable raw mode: {e})");
+        process::exit(1);
+    }
+    // Terminals without an alternate screen are drawn on inline (i.e. on the main screen) instead
+    let alt_screen = execute!(io::stdout(), EnterAlternateScreen).is_ok();
+    let mouse_capture = args.mouse && execute!(io::stdout(), EnableMouseCapture).is_ok();
     let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    if !alt_screen {
+        terminal.clear()?;
+    }
 
-    fn term_restore() -> io::Result<()> {
+    fn term_restore(alt_screen: bool, mouse_capture: bool) -> io::Result<()> {
         disable_raw_mode()?;
-        execute!(io::stdout(), LeaveAlternateScreen)?;
+        if mouse_capture {
+            execute!(io::stdout(), DisableMouseCapture)?;
+        }
+        if alt_screen {
+            execute!(io::stdout(), LeaveAlternateScreen)?;
+        }
         Ok(())
     }
 
     let default_panic_hook = take_hook();
     set_hook(Box::new(move |panic_info| {
-        let _ = term_restore();
+        let _ = term_restore(alt_screen, mouse_capture);
         default_panic_hook(panic_info);
     }));
 
-    App::default()
-        .run(&mut terminal, args.feeds_file, tick_rate, args.show_fps)
-        .await?;
+    let image_picker = match config.show_images {
+        true => ImageCache::query_picker(),
+        false => None,
+    };
+    // The terminal is restored even if the app fails, before the error is reported
+    let result = App::new(config, image_picker, perf_log)
+        .run(&mut terminal)
+        .await;
 
-    term_restore()?;
+    if !alt_screen {
+        terminal.clear()?;
+    }
+    term_restore(alt_screen, mouse_capture)?;
 
-    Ok(())
+    result
 }
@@ -0,0 +1,97 @@
+// Minimal OPML (https://opml.org/spec2.opml) support for sharing subscriptions with other readers
+
+use quick_xml::{Reader, events::Event};
+
+pub(crate) struct OpmlFeed<'a> {
+    pub title: &'a str,
+    pub url: &'a str,
+}
+
+pub(crate) fn export(feeds: &[OpmlFeed]) -> String {
+    let outlines: String = feeds
+        .iter()
+        .map(|feed| {
+            format!(
+                "    <outline type=\"rss\" text=\"{title}\" title=\"{title}\" xmlUrl=\"{url}\"/>\n",
+                title = escape_xml(feed.title),
+                url = escape_xml(feed.url)
+            )
+        })
+        .collect();
+
+    format!(
+        concat!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+            "<opml version=\"2.0\">\n",
+            "  <head>\n",
+            "    <title>{} subscriptions</title>\n",
+            "  </head>\n",
+            "  <body>\n",
+            "{}",
+            "  </body>\n",
+            "</opml>\n"
+        ),
+        env!("CARGO_PKG_NAME"),
+        outlines
+    )
+}
+
+// Feed URLs (`xmlUrl`) of every outline in an OPML document, including outlines nested in categories
+pub(crate) fn import(opml: &str) -> Result<Vec<String>, String> {
+    let mut reader = Reader::from_str(opml);
+    let mut is_opml = false;
+    let mut feed_urls = Vec::new();
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("malformed OPML at byte {}: {}", reader.error_position(), e))?;
+        match event {
+            Event::Start(ref tag) | Event::Empty(ref tag) => match tag.local_name().as_ref() {
+                b"opml" => is_opml = true,
+                b"outline" => {
+                    let xml_url = tag
+                        .attributes()
+                        .flatten()
+                        // Some exporters do not follow the attribute's camel case
+                        .find(|attr| {
+                            attr.key
+                                .local_name()
+                                .as_ref()
+                                .eq_ignore_ascii_case(b"xmlUrl")
+                        })
+                        .map(|attr| {
+                            attr.decode_and_unescape_value(reader.decoder())
+                                .map(|url| url.trim().to_owned())
+                        })
+                        .transpose()
+                        .map_err(|e| format!("malformed OPML outline: {}", e))?;
+                    feed_urls.extend(xml_url.filter(|url| !url.is_empty()));
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    match is_opml {
+        true => Ok(feed_urls),
+        false => Err("not an OPML document (no <opml> element)".to_string()),
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut escaped, c| {
+            match c {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&apos;"),
+                c => escaped.push(c),
+            }
+            escaped
+        })
+}
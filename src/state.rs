@@ -0,0 +1,99 @@
+use std::{collections::BTreeMap, fs, io, num::NonZeroU64, path::Path};
+
+use chrono::TimeDelta;
+use serde::{Deserialize, Serialize};
+
+// Items that have not been in the feed for this long are forgotten, so that the state file does not keep
+// every item that was ever fetched
+const RETENTION: TimeDelta = TimeDelta::days(90);
+
+// Session state persisted across launches, i.e. the flags of every known item keyed by item id
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct State {
+    #[serde(default)]
+    pub items: BTreeMap<NonZeroU64, ItemState>,
+}
+
+#[derive(Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub(crate) struct ItemState {
+    pub read: bool,
+    // Whether the item was in the feed in a previous session (i.e. it is not new)
+    pub seen: bool,
+    // When the item was last in the feed, as a Unix timestamp, or `None` in state files written before
+    // this was recorded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_seen: Option<i64>,
+}
+
+impl State {
+    // Missing or malformed state files are treated as empty rather than as errors, as the state is not
+    // critical for the app to function
+    pub(crate) fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    // Write the state to a temporary file first and then move it into place, so that an interrupted
+    // write never leaves behind a truncated state file
+    pub(crate) fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent_dir) = path.parent() {
+            fs::create_dir_all(parent_dir)?;
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_vec(self)?)?;
+        fs::rename(tmp_path, path)
+    }
+
+    // Forget the items that were last in the feed longer than `RETENTION` before `now` (a Unix timestamp)
+    pub(crate) fn prune(&mut self, now: i64) {
+        let oldest = now - RETENTION.num_seconds();
+        self.items.retain(|_, item_state| {
+            item_state
+                .last_seen
+                .is_none_or(|last_seen| last_seen >= oldest)
+        });
+    }
+
+    // Ids of the items whose state matches `predicate`
+    pub(crate) fn ids_where(
+        &self,
+        predicate: impl Fn(&ItemState) -> bool,
+    ) -> impl Iterator<Item = NonZeroU64> {
+        self.items
+            .iter()
+            .filter(move |(_, item_state)| predicate(item_state))
+            .map(|(id, _)| *id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prune_forgets_items_not_seen_within_retention() {
+        let now = 1_760_000_000;
+        let id = |id| NonZeroU64::new(id).unwrap();
+        let item_state = |last_seen| ItemState {
+            read: true,
+            seen: true,
+            last_seen,
+        };
+        let mut state = State {
+            items: BTreeMap::from([
+                (id(1), item_state(Some(now))),
+                (id(2), item_state(Some(now - RETENTION.num_seconds()))),
+                (id(3), item_state(Some(now - RETENTION.num_seconds() - 1))),
+                (id(4), item_state(None)),
+            ]),
+        };
+        state.prune(now);
+        assert_eq!(
+            state.items.keys().copied().collect::<Vec<_>>(),
+            [id(1), id(2), id(4)]
+        );
+    }
+}
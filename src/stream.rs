@@ -1,50 +1,182 @@
-use std::{io, pin::Pin, task::Poll, time::Duration};
+use std::{collections::HashMap, io, pin::Pin, task::Poll, time::Duration};
 
-use crossterm::event::{Event, EventStream, KeyCode, KeyEvent};
+use crossterm::event::{Event, EventStream, KeyCode, KeyEvent, MouseEvent, MouseEventKind};
+use tokio::time::{Instant, Sleep};
 use tokio_stream::Stream;
 
-pub(crate) struct RateLimitedEventStream {
-    _inner: Pin<Box<EventStream>>,
-    _timer: Option<Pin<Box<tokio::time::Sleep>>>,
+// Which terminal events are rate-limited, and by how much
+pub(crate) struct RateLimits {
+    pub is_rate_limited: Box<dyn Fn(&Event) -> bool + Send>,
+    // Delay of rate-limited key events by key (e.g. a longer one for a heavier action), falling back to
+    // `delay` for other keys and non-key events
+    pub key_delays: HashMap<KeyCode, Duration>,
+    pub delay: Duration,
+}
+
+impl RateLimits {
+    // Events are rate-limited together with the events of the same delay group, i.e. a key with a delay of
+    // its own, or otherwise (`None`) every other event, so that alternating keys (e.g. Up and Down) cannot
+    // get around the default delay
+    fn delay_group_of(&self, event: &Event) -> Option<KeyCode> {
+        match event {
+            Event::Key(KeyEvent { code, .. }) if self.key_delays.contains_key(code) => Some(*code),
+            _ => None,
+        }
+    }
 
-    delay: Duration, // Duration to wait before allowing rate-limited events to be emitted
-    pending_event: Option<io::Result<Event>>,
-    can_emit: bool,
+    fn delay_of(&self, event: &Event) -> Duration {
+        match event {
+            Event::Key(KeyEvent { code, .. }) => self.key_delays.get(code).copied(),
+            _ => None,
+        }
+        .unwrap_or(self.delay)
+    }
 }
 
-impl RateLimitedEventStream {
-    // TODO: allow users to specify event specific delays + generic event filter instead of hardcoding
-    pub fn new(delay: Duration) -> Self {
-        RateLimitedEventStream {
-            _inner: Box::pin(EventStream::default()),
-            _timer: None,
-            delay,
-            pending_event: None,
-            can_emit: true,
+/*
+ By default, only scroll events (up/down/mouse scroll) are rate-limited, together, to 15ms. Mouse clicks
+ are not, as a held back click would break up a double click.
+
+ Delay of 15ms maintains smooth scrolling (1s/15ms = 66.67 FPS) while preventing event flooding
+ from high-sensitivity mice (e.g. MX Master's fast scroll wheel).
+*/
+impl Default for RateLimits {
+    fn default() -> Self {
+        RateLimits {
+            // NOTE: mouse scroll events are interpreted as KeyCode::Up and KeyCode::Down, unless the mouse
+            // is captured (with `--mouse`)
+            is_rate_limited: Box::new(|event| {
+                matches!(
+                    event,
+                    Event::Key(KeyEvent {
+                        code: KeyCode::Up | KeyCode::Down,
+                        ..
+                    }) | Event::Mouse(MouseEvent {
+                        kind: MouseEventKind::ScrollUp | MouseEventKind::ScrollDown,
+                        ..
+                    })
+                )
+            }),
+            key_delays: HashMap::new(),
+            delay: Duration::from_millis(15),
+        }
+    }
+}
+
+// Rate-limiting state of the events of a delay group
+struct Limiter {
+    // Until when the events cannot be emitted
+    until: Instant,
+    pending_event: Option<Event>,
+}
+
+pub(crate) enum Decision {
+    Emit(Event),
+    // Held back until the delay of its group has passed (see `Debouncer::pop_due`)
+    Hold,
+}
+
+// Leading + trailing debouncer, for each delay group separately: the first event of a group is emitted
+// right away, whereas the events that follow within its delay are held back, and only the most recent of
+// them is emitted once the delay has passed. Independent of the terminal and of the clock, as the time is
+// passed in
+pub(crate) struct Debouncer {
+    limits: RateLimits,
+    // By delay group (see `RateLimits::delay_group_of`), in the order the groups were first limited, so
+    // that events due at the same time are released in a deterministic order
+    limiters: Vec<(Option<KeyCode>, Limiter)>,
+}
+
+impl Debouncer {
+    pub fn new(limits: RateLimits) -> Self {
+        Debouncer {
+            limits,
+            limiters: Vec::new(),
+        }
+    }
+
+    // Decide whether an event that came in at `now` is emitted right away or held back, where a held back
+    // event replaces the one already held back for its group (if any)
+    pub fn push(&mut self, now: Instant, event: Event) -> Decision {
+        // Non-rate-limited events pass through immediately
+        if !(self.limits.is_rate_limited)(&event) {
+            return Decision::Emit(event);
+        }
+        let group = self.limits.delay_group_of(&event);
+        let until = now + self.limits.delay_of(&event);
+        match (self.limiters.iter_mut()).find(|(limiter_group, _)| *limiter_group == group) {
+            Some((_, limiter)) if now < limiter.until || limiter.pending_event.is_some() => {
+                // Only store most recent event and discard/ignore others that came during the delay
+                limiter.pending_event = Some(event);
+                Decision::Hold
+            }
+            Some((_, limiter)) => {
+                limiter.until = until;
+                Decision::Emit(event)
+            }
+            None => {
+                let limiter = Limiter {
+                    until,
+                    pending_event: None,
+                };
+                self.limiters.push((group, limiter));
+                Decision::Emit(event)
+            }
         }
     }
 
-    fn start_timer(&mut self) {
-        self._timer = Some(Box::pin(tokio::time::sleep(self.delay)));
+    // Take the earliest held back event whose delay has passed by `now`, which (as it is emitted) delays
+    // the events of its group again
+    pub fn pop_due(&mut self, now: Instant) -> Option<Event> {
+        // Limiters that can emit again are only kept for as long as they rate-limit
+        self.limiters
+            .retain(|(_, limiter)| now < limiter.until || limiter.pending_event.is_some());
+        let limiter = (self.limiters.iter_mut())
+            .map(|(_, limiter)| limiter)
+            .filter(|limiter| limiter.until <= now && limiter.pending_event.is_some())
+            .min_by_key(|limiter| limiter.until)?;
+        let event = limiter.pending_event.take()?;
+        limiter.until = now + self.limits.delay_of(&event);
+        Some(event)
     }
 
-    fn remove_timer(&mut self) {
-        self._timer = None;
+    // When the next held back event is due
+    pub fn next_due(&self) -> Option<Instant> {
+        (self.limiters.iter())
+            .filter(|(_, limiter)| limiter.pending_event.is_some())
+            .map(|(_, limiter)| limiter.until)
+            .min()
     }
 
-    fn should_rate_limit(&self, event: &<EventStream as Stream>::Item) -> bool {
-        match event {
-            // NOTE: mouse scroll events are interpreted as KeyCode::Up and KeyCode::Down
-            Ok(Event::Key(KeyEvent {
-                code: KeyCode::Up | KeyCode::Down,
-                ..
-            })) => true,
-            _ => false,
+    // Take the earliest held back event regardless of its delay, e.g. once no more events come in
+    pub fn take_pending(&mut self) -> Option<Event> {
+        (self.limiters.iter_mut())
+            .map(|(_, limiter)| limiter)
+            .filter(|limiter| limiter.pending_event.is_some())
+            .min_by_key(|limiter| limiter.until)?
+            .pending_event
+            .take()
+    }
+}
+
+pub(crate) struct RateLimitedEventStream {
+    _inner: Pin<Box<EventStream>>,
+    // Running until the next held back event is due
+    _timer: Option<Pin<Box<Sleep>>>,
+
+    debouncer: Debouncer,
+}
+
+impl RateLimitedEventStream {
+    pub fn new(limits: RateLimits) -> Self {
+        RateLimitedEventStream {
+            _inner: Box::pin(EventStream::default()),
+            _timer: None,
+            debouncer: Debouncer::new(limits),
         }
     }
 }
 
-// Behavior is similar to a leading + trailing debouncer
 impl Stream for RateLimitedEventStream {
     type Item = io::Result<Event>;
 
@@ -52,49 +184,208 @@ impl Stream for RateLimitedEventStream {
         mut self: Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> Poll<Option<Self::Item>> {
-        if let Some(ref mut timer) = self._timer {
-            if timer.as_mut().poll(cx).is_ready() {
-                // Timer has completed, reset it and allow emitting events again
-                self.remove_timer();
-                self.can_emit = true;
-                if let Some(event) = self.pending_event.take() {
-                    self.can_emit = false;
-                    self._timer = Some(Box::pin(tokio::time::sleep(self.delay)));
-                    return Poll::Ready(Some(event));
-                }
+        loop {
+            if let Some(event) = self.debouncer.pop_due(Instant::now()) {
+                return Poll::Ready(Some(Ok(event)));
             }
-        }
 
-        loop {
-            match self._inner.as_mut().poll_next(cx) {
-                Poll::Ready(Some(event)) => {
-                    match self.should_rate_limit(&event) {
-                        // Event matches the filter, handle rate limiting
-                        true => {
-                            if self.can_emit {
-                                self.can_emit = false;
-                                self.start_timer();
-                                return Poll::Ready(Some(event));
-                            } else {
-                                // Only store most recent event and discard/ignore others that came during the delay
-                                self.pending_event = Some(event);
-                                // Continue polling/draining the inner stream to not accumulate backpressure
-                            }
+            // Continue polling/draining the inner stream to not accumulate backpressure
+            loop {
+                match self._inner.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(Ok(event))) => {
+                        match self.debouncer.push(Instant::now(), event) {
+                            Decision::Emit(event) => return Poll::Ready(Some(Ok(event))),
+                            Decision::Hold => {}
                         }
-                        // Non-rate-limited events pass through immediately
-                        false => return Poll::Ready(Some(event)),
                     }
-                }
-                Poll::Ready(None) => {
-                    if let Some(event) = self.pending_event.take() {
-                        return Poll::Ready(Some(event));
-                    } else {
-                        return Poll::Ready(None);
+                    Poll::Ready(Some(Err(e))) => return Poll::Ready(Some(Err(e))),
+                    Poll::Ready(None) => {
+                        return Poll::Ready(self.debouncer.take_pending().map(Ok));
                     }
+                    Poll::Pending => break,
                 }
-                Poll::Pending => break,
             }
+
+            let Some(due) = self.debouncer.next_due() else {
+                self._timer = None;
+                return Poll::Pending;
+            };
+            let timer = self
+                ._timer
+                .get_or_insert_with(|| Box::pin(tokio::time::sleep_until(due)));
+            if timer.deadline() != due {
+                timer.as_mut().reset(due);
+            }
+            if timer.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            // The next held back event is due (i.e. is emitted at the top of the loop)
         }
-        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::from(code))
+    }
+
+    fn is_emit(decision: Decision, expected: &Event) -> bool {
+        matches!(decision, Decision::Emit(event) if event == *expected)
+    }
+
+    #[test]
+    fn burst_emits_first_and_one_trailing_event() {
+        let mut debouncer = Debouncer::new(RateLimits::default());
+        let start = Instant::now();
+
+        assert!(is_emit(
+            debouncer.push(start, key(KeyCode::Up)),
+            &key(KeyCode::Up)
+        ));
+        for ms in 1..10 {
+            let now = start + Duration::from_millis(ms);
+            assert!(matches!(
+                debouncer.push(now, key(KeyCode::Up)),
+                Decision::Hold
+            ));
+        }
+
+        let due = debouncer.next_due().expect("an event is held back");
+        assert_eq!(debouncer.pop_due(due), Some(key(KeyCode::Up)));
+        // The rest of the burst was dropped in favour of the most recent event
+        assert_eq!(debouncer.pop_due(due + Duration::from_secs(1)), None);
+        assert_eq!(debouncer.next_due(), None);
+    }
+
+    #[test]
+    fn trailing_event_is_released_once_due() {
+        let mut debouncer = Debouncer::new(RateLimits::default());
+        let start = Instant::now();
+        debouncer.push(start, key(KeyCode::Down));
+        debouncer.push(start + Duration::from_millis(5), key(KeyCode::Down));
+
+        let due = debouncer.next_due().expect("an event is held back");
+        assert_eq!(due, start + Duration::from_millis(15));
+        assert_eq!(debouncer.pop_due(due - Duration::from_millis(1)), None);
+        assert_eq!(debouncer.pop_due(due), Some(key(KeyCode::Down)));
+    }
+
+    #[test]
+    fn alternating_scroll_keys_share_the_delay() {
+        let mut debouncer = Debouncer::new(RateLimits::default());
+        let start = Instant::now();
+
+        assert!(is_emit(
+            debouncer.push(start, key(KeyCode::Up)),
+            &key(KeyCode::Up)
+        ));
+        for (ms, code) in [(4, KeyCode::Down), (8, KeyCode::Up), (12, KeyCode::Down)] {
+            let now = start + Duration::from_millis(ms);
+            assert!(matches!(debouncer.push(now, key(code)), Decision::Hold));
+        }
+
+        let due = debouncer.next_due().expect("an event is held back");
+        assert_eq!(due, start + Duration::from_millis(15));
+        assert_eq!(debouncer.pop_due(due), Some(key(KeyCode::Down)));
+        assert_eq!(debouncer.pop_due(due), None);
+    }
+
+    #[test]
+    fn non_rate_limited_event_passes_through_while_scroll_is_held() {
+        let mut debouncer = Debouncer::new(RateLimits::default());
+        let start = Instant::now();
+        debouncer.push(start, key(KeyCode::Up));
+        let now = start + Duration::from_millis(1);
+        assert!(matches!(
+            debouncer.push(now, key(KeyCode::Up)),
+            Decision::Hold
+        ));
+
+        let enter = key(KeyCode::Enter);
+        assert!(is_emit(debouncer.push(now, enter.clone()), &enter));
+        // The held back scroll event is unaffected
+        let due = debouncer.next_due().expect("an event is held back");
+        assert_eq!(debouncer.pop_due(due), Some(key(KeyCode::Up)));
+    }
+
+    // Rate-limits `r` (refresh) for 1s and every other character key for 100ms
+    fn refresh_limits() -> RateLimits {
+        RateLimits {
+            is_rate_limited: Box::new(|event| {
+                matches!(
+                    event,
+                    Event::Key(KeyEvent {
+                        code: KeyCode::Char(_),
+                        ..
+                    })
+                )
+            }),
+            key_delays: HashMap::from([(KeyCode::Char('r'), Duration::from_secs(1))]),
+            delay: Duration::from_millis(100),
+        }
+    }
+
+    #[test]
+    fn predicate_decides_which_events_are_rate_limited() {
+        let mut debouncer = Debouncer::new(refresh_limits());
+        let start = Instant::now();
+
+        // Up and Down are not rate-limited by these limits, unlike by default
+        for _ in 0..3 {
+            let up = key(KeyCode::Up);
+            assert!(is_emit(debouncer.push(start, up.clone()), &up));
+        }
+        debouncer.push(start, key(KeyCode::Char('j')));
+        assert!(matches!(
+            debouncer.push(start, key(KeyCode::Char('j'))),
+            Decision::Hold
+        ));
+    }
+
+    #[test]
+    fn keys_are_delayed_by_their_own_delay() {
+        let mut debouncer = Debouncer::new(refresh_limits());
+        let start = Instant::now();
+        for code in [KeyCode::Char('r'), KeyCode::Char('j')] {
+            debouncer.push(start, key(code));
+            debouncer.push(start, key(code));
+        }
+
+        // The key without a delay of its own falls back to the default delay
+        assert_eq!(
+            debouncer.next_due(),
+            Some(start + Duration::from_millis(100))
+        );
+        let after_default = start + Duration::from_millis(100);
+        assert_eq!(
+            debouncer.pop_due(after_default),
+            Some(key(KeyCode::Char('j')))
+        );
+        assert_eq!(debouncer.pop_due(after_default), None);
+
+        let after_refresh = start + Duration::from_secs(1);
+        assert_eq!(debouncer.next_due(), Some(after_refresh));
+        assert_eq!(
+            debouncer.pop_due(after_refresh),
+            Some(key(KeyCode::Char('r')))
+        );
+    }
+
+    #[test]
+    fn due_events_are_released_earliest_first() {
+        let mut debouncer = Debouncer::new(refresh_limits());
+        let start = Instant::now();
+        for code in [KeyCode::Char('r'), KeyCode::Char('j')] {
+            debouncer.push(start, key(code));
+            debouncer.push(start, key(code));
+        }
+
+        // Both are due, but `j` was due first
+        let later = start + Duration::from_secs(2);
+        assert_eq!(debouncer.pop_due(later), Some(key(KeyCode::Char('j'))));
+        assert_eq!(debouncer.pop_due(later), Some(key(KeyCode::Char('r'))));
     }
 }
@@ -1,11 +1,175 @@
-use std::time::{Duration, Instant};
+use std::{
+    borrow::Cow,
+    error::Error,
+    fmt, fs,
+    hash::{Hash, Hasher},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::Stdio,
+    str::FromStr,
+    time::{Duration, Instant},
+};
 
-use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::StatefulWidget};
-use textwrap::{Options, wrap};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use clap::ValueEnum;
+use flate2::read::GzDecoder;
+use html2text::{
+    Element, Handle,
+    render::{PlainDecorator, TaggedLine, TextDecorator},
+};
+use itertools::Itertools;
+use ratatui::{
+    backend::Backend,
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    style::Color,
+    widgets::StatefulWidget,
+};
+use reqwest::Client;
+use serde::Deserialize;
+use siphasher::sip::SipHasher13;
+use textwrap::{Options, core::display_width, wrap};
 use throbber_widgets_tui::{Throbber as TuiThrobber, ThrobberState as TuiThrobberState};
+use tokio::io::AsyncWriteExt;
+use url::Url;
+
+use crate::images::{IMAGE_MARKER, image_line, split_image_lines};
 
 pub const LONG_TIMESTAMP_FMT: &str = "%H:%M:%S / %-e-%b-%Y [%a]";
-pub const WARM_WHITE_RGB: Color = Color::Rgb(232, 233, 240);
+pub const SHORT_TIMESTAMP_FMT: &str = "%-e %b %Y, %H:%M";
+
+// Loose formats of feed dates that are neither RFC 2822 nor RFC 3339, with an offset, without one (taken
+// as UTC), and without a time (taken as midnight UTC). `%B` parses abbreviated (e.g. "Oct") as well as full
+// (e.g. "October") month names
+const LOOSE_DATE_TIME_FMTS: [&str; 4] = [
+    // e.g. "2026-10-14 08:00:00 +0200" or "2026-10-14T08:00:00+0200"
+    "%Y-%m-%d %H:%M:%S%.f %z",
+    "%Y-%m-%dT%H:%M:%S%.f%z",
+    // e.g. "14 Oct 2026 08:00:00 +0200" (RFC 2822 with a 4-digit year but other deviations)
+    "%d %B %Y %H:%M:%S %z",
+    "%d %B %Y %H:%M %z",
+];
+const LOOSE_NAIVE_DATE_TIME_FMTS: [&str; 5] = [
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M",
+    "%d %B %Y %H:%M:%S",
+];
+const LOOSE_DATE_FMTS: [&str; 4] = ["%Y-%m-%d", "%Y/%m/%d", "%d %B %Y", "%B %d, %Y"];
+
+// Colors of the key elements of the UI, where other colors (e.g. red errors) carry meaning and are fixed
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Theme {
+    // Item titles
+    pub title: Color,
+    // Item URLs in the list
+    pub url: Color,
+    pub pub_date: Color,
+    // Selected row marker, group headers and the search prompt
+    pub highlight: Color,
+    // Key hints in the footer
+    pub help: Color,
+    // Content of the expanded item
+    pub content: Color,
+}
+
+impl Theme {
+    const DARK: Self = Self {
+        title: Color::White,
+        url: Color::Reset,
+        pub_date: Color::Yellow,
+        highlight: Color::Magenta,
+        help: Color::Rgb(100, 116, 139),
+        content: Color::Rgb(232, 233, 240),
+    };
+
+    const LIGHT: Self = Self {
+        title: Color::Black,
+        url: Color::Blue,
+        pub_date: Color::Rgb(161, 98, 7),
+        highlight: Color::Magenta,
+        help: Color::Rgb(100, 116, 139),
+        content: Color::Rgb(30, 41, 59),
+    };
+
+    pub(crate) fn preset(preset: ThemePreset) -> Self {
+        match preset {
+            ThemePreset::Dark => Self::DARK,
+            ThemePreset::Light => Self::LIGHT,
+        }
+    }
+
+    // Load the colors of a theme file on top of a preset, where colors that are not in the file keep
+    // those of the preset. A missing file is the same as an empty one
+    pub(crate) fn load(path: &Path, preset: ThemePreset) -> Result<Self, ThemeError> {
+        let mut theme = Self::preset(preset);
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(theme),
+            Err(e) => return Err(ThemeError::Io(e)),
+        };
+        let theme_file: ThemeFile = toml::from_str(&content).map_err(ThemeError::Toml)?;
+
+        for (name, color, spec) in [
+            ("title", &mut theme.title, theme_file.title),
+            ("url", &mut theme.url, theme_file.url),
+            ("pub_date", &mut theme.pub_date, theme_file.pub_date),
+            ("highlight", &mut theme.highlight, theme_file.highlight),
+            ("help", &mut theme.help, theme_file.help),
+            ("content", &mut theme.content, theme_file.content),
+        ] {
+            if let Some(spec) = spec {
+                *color = Color::from_str(&spec).map_err(|_| ThemeError::InvalidColor {
+                    element: name,
+                    spec,
+                })?;
+            }
+        }
+        Ok(theme)
+    }
+}
+
+// Built-in themes, for dark and light terminal backgrounds
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub(crate) enum ThemePreset {
+    #[default]
+    Dark,
+    Light,
+}
+
+// Colors are names (e.g. "yellow", "light-blue"), hex RGB values (e.g. "#64748b") or ANSI indexes (e.g. "8")
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeFile {
+    title: Option<String>,
+    url: Option<String>,
+    pub_date: Option<String>,
+    highlight: Option<String>,
+    help: Option<String>,
+    content: Option<String>,
+}
+
+#[derive(Debug)]
+pub(crate) enum ThemeError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    InvalidColor { element: &'static str, spec: String },
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::Io(e) => write!(f, "{}", e),
+            ThemeError::Toml(e) => write!(f, "{}", e),
+            ThemeError::InvalidColor { element, spec } => {
+                write!(f, "invalid color \"{}\" for `{}`", spec, element)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
 
 pub(crate) fn wrap_then_apply<T>(text: &str, width: usize, apply: fn(String) -> T) -> Vec<T> {
     wrap(text, Options::new(width).break_words(true))
@@ -14,17 +178,569 @@ pub(crate) fn wrap_then_apply<T>(text: &str, width: usize, apply: fn(String) ->
         .collect()
 }
 
+// Truncate `text` to a single line of `width`, ending in an ellipsis if anything was cut off
+pub(crate) fn truncate_then_apply<T>(text: &str, width: usize, apply: fn(String) -> T) -> Vec<T> {
+    let truncated = if text.chars().count() <= width {
+        text.to_string()
+    } else {
+        let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+        if width > 0 {
+            truncated.push('…');
+        }
+        truncated
+    };
+    vec![apply(truncated)]
+}
+
+// Wrap `text`, unless `width` is below `min_width` where wrapping would result in an unreadable sliver
+// (e.g. a character per line), in which case it is truncated to a single line instead
+pub(crate) fn wrap_or_truncate_then_apply<T>(
+    text: &str,
+    width: usize,
+    min_width: usize,
+    apply: fn(String) -> T,
+) -> Vec<T> {
+    if width < min_width {
+        truncate_then_apply(text, width, apply)
+    } else {
+        wrap_then_apply(text, width, apply)
+    }
+}
+
+// Same as `wrap_then_apply`, but wrapped lines are indented to match the leading whitespace of `text`,
+// preserving intentional indentation (e.g. code or ASCII art)
+pub(crate) fn wrap_indented_then_apply<T>(
+    text: &str,
+    width: usize,
+    apply: fn(String) -> T,
+) -> Vec<T> {
+    let indent = &text[..text.len() - text.trim_start().len()];
+    // Indentation that leaves no room for the text itself is ignored
+    if indent.chars().count() >= width {
+        return wrap_then_apply(text, width, apply);
+    }
+    wrap(
+        text,
+        Options::new(width)
+            .break_words(true)
+            .subsequent_indent(indent),
+    )
+    .into_iter()
+    .map(|line_str| apply(line_str.to_string()))
+    .collect()
+}
+
+// Plain text (as `html2text::config::plain`), except that images are kept as image lines (see
+// `images::image_line`) for the expanded view to show
+#[derive(Clone)]
+struct ImageLineDecorator(PlainDecorator);
+
+impl TextDecorator for ImageLineDecorator {
+    type Annotation = ();
+
+    fn decorate_image(&mut self, src: &str, title: &str) -> (String, Self::Annotation) {
+        // Images without alt text were given the marker as a placeholder by `try_parse_html`
+        (image_line(src, title.trim_matches(IMAGE_MARKER)), ())
+    }
+
+    fn make_subblock_decorator(&self) -> Self {
+        Self(self.0.make_subblock_decorator())
+    }
+
+    fn finalise(&mut self, urls: Vec<String>) -> Vec<TaggedLine<Self::Annotation>> {
+        self.0.finalise(urls)
+    }
+
+    fn decorate_link_start(&mut self, url: &str) -> (String, Self::Annotation) {
+        self.0.decorate_link_start(url)
+    }
+    fn decorate_link_end(&mut self) -> String {
+        self.0.decorate_link_end()
+    }
+    fn decorate_em_start(&self) -> (String, Self::Annotation) {
+        self.0.decorate_em_start()
+    }
+    fn decorate_em_end(&self) -> String {
+        self.0.decorate_em_end()
+    }
+    fn decorate_strong_start(&self) -> (String, Self::Annotation) {
+        self.0.decorate_strong_start()
+    }
+    fn decorate_strong_end(&self) -> String {
+        self.0.decorate_strong_end()
+    }
+    fn decorate_strikeout_start(&self) -> (String, Self::Annotation) {
+        self.0.decorate_strikeout_start()
+    }
+    fn decorate_strikeout_end(&self) -> String {
+        self.0.decorate_strikeout_end()
+    }
+    fn decorate_code_start(&self) -> (String, Self::Annotation) {
+        self.0.decorate_code_start()
+    }
+    fn decorate_code_end(&self) -> String {
+        self.0.decorate_code_end()
+    }
+    fn decorate_preformat_first(&self) -> Self::Annotation {
+        self.0.decorate_preformat_first()
+    }
+    fn decorate_preformat_cont(&self) -> Self::Annotation {
+        self.0.decorate_preformat_cont()
+    }
+    fn header_prefix(&self, level: usize) -> String {
+        self.0.header_prefix(level)
+    }
+    fn quote_prefix(&self) -> String {
+        self.0.quote_prefix()
+    }
+    fn unordered_item_prefix(&self) -> String {
+        self.0.unordered_item_prefix()
+    }
+    fn ordered_item_prefix(&self, i: i64) -> String {
+        self.0.ordered_item_prefix(i)
+    }
+}
+
+// `html2text` drops images without alt text, so they are given a placeholder alt text instead
+fn add_placeholder_alts(node: &Handle) {
+    if let Element { name, attrs, .. } = &node.data
+        && name.local.as_ref() == "img"
+    {
+        let mut attrs = attrs.borrow_mut();
+        match attrs
+            .iter_mut()
+            .find(|attr| attr.name.local.as_ref() == "alt")
+        {
+            Some(alt) if alt.value.trim().is_empty() => alt.value = IMAGE_MARKER.to_string().into(),
+            Some(_) => {}
+            None => {
+                if let Some(src) = attrs.iter().find(|attr| attr.name.local.as_ref() == "src") {
+                    let mut alt = src.clone();
+                    alt.name.local = "alt".into();
+                    alt.value = IMAGE_MARKER.to_string().into();
+                    attrs.push(alt);
+                }
+            }
+        }
+    }
+    for child in node.children.borrow().iter() {
+        add_placeholder_alts(child);
+    }
+}
+
 pub(crate) fn try_parse_html(html: &str) -> Vec<String> {
-    html2text::config::plain()
+    let config = html2text::config::with_decorator(ImageLineDecorator(PlainDecorator::new()))
+        .do_decorate()
         .no_link_wrapping()
-        .link_footnotes(true)
-        // `html2text` does provide a `lines_from_read` method, however there isn't a good way to convert
-        // lines to to `Vec<String>` directly.
-        .string_from_read(html.as_bytes(), usize::MAX)
-        .map(|text| text.lines().map(str::to_owned).collect())
+        .link_footnotes(true);
+    // `html2text` does provide a `lines_from_read` method, however there isn't a good way to convert
+    // lines to to `Vec<String>` directly.
+    config
+        .parse_html(html.as_bytes())
+        .and_then(|dom| {
+            add_placeholder_alts(&dom.document);
+            config.dom_to_render_tree(&dom)
+        })
+        .and_then(|render_tree| config.render_to_string(render_tree, usize::MAX))
+        .map(|text| text.lines().flat_map(split_image_lines).collect())
         .unwrap_or(vec![html.to_owned()])
 }
 
+// URLs of the link footnotes (e.g. "[1]: https://...") that `try_parse_html` appends to the text, in order
+// of their numbers
+pub(crate) fn footnote_links(lines: &[String]) -> Vec<String> {
+    // Footnotes are the trailing lines, i.e. numbered N, N-1, ..., 1 when read from the end
+    let trailing: Vec<(usize, &str)> = lines
+        .iter()
+        .rev()
+        .map_while(|line| {
+            let (number, url) = line.strip_prefix('[')?.split_once("]: ")?;
+            Some((number.parse().ok()?, url))
+        })
+        .collect();
+    let count = trailing.first().map_or(0, |(number, _)| *number);
+    let is_footnotes = trailing.len() >= count
+        && (trailing.iter().take(count))
+            .enumerate()
+            .all(|(i, (number, _))| *number == count - i);
+    if !is_footnotes {
+        return Vec::new();
+    }
+    trailing
+        .into_iter()
+        .take(count)
+        .rev()
+        .map(|(_, url)| url.to_owned())
+        .collect()
+}
+
+// A URL in wrapped text, with the (line, column, width) of each of the lines it is wrapped over
+#[derive(Clone)]
+pub(crate) struct WrappedUrl {
+    pub url: String,
+    pub parts: Vec<(usize, usize, usize)>,
+}
+
+// URLs (i.e. http and https ones) in `lines` of text wrapped to `width`, where a URL that runs up to the
+// end of a full line is taken to be broken there and to continue at the start of the next line
+pub(crate) fn find_wrapped_urls(lines: &[String], width: usize) -> Vec<WrappedUrl> {
+    // Punctuation that rather ends the sentence (or parenthesis) around a URL than the URL itself
+    const TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?', ')', ']', '\'', '"'];
+
+    let mut urls: Vec<WrappedUrl> = Vec::new();
+    let mut continued = false;
+    for (line_i, line) in lines.iter().enumerate() {
+        let is_full = display_width(line) >= width;
+        let mut search_from = 0;
+        let part_len = line.find(char::is_whitespace).unwrap_or(line.len());
+        // A line that starts with what cannot be part of a URL (e.g. the "[2]:" of the next footnote) does
+        // not continue one
+        let continues = !line[..part_len].contains(['[', ']', '<', '>', '"']);
+        if std::mem::take(&mut continued)
+            && continues
+            && let Some(url) = urls.last_mut()
+        {
+            let part = &line[..part_len];
+            continued = part_len == line.len() && is_full;
+            let part = match continued {
+                true => part,
+                false => part.trim_end_matches(TRAILING_PUNCTUATION),
+            };
+            if !part.is_empty() {
+                url.url.push_str(part);
+                url.parts.push((line_i, 0, display_width(part)));
+            }
+            search_from = part_len;
+        }
+        while let Some(start) = line[search_from..]
+            .match_indices("http")
+            .map(|(i, _)| search_from + i)
+            .find(|&i| line[i..].starts_with("http://") || line[i..].starts_with("https://"))
+        {
+            let end = line[start..]
+                .find(char::is_whitespace)
+                .map_or(line.len(), |len| start + len);
+            continued = end == line.len() && is_full;
+            let url = match continued {
+                true => &line[start..end],
+                false => line[start..end].trim_end_matches(TRAILING_PUNCTUATION),
+            };
+            urls.push(WrappedUrl {
+                url: url.to_owned(),
+                parts: vec![(line_i, display_width(&line[..start]), display_width(url))],
+            });
+            search_from = end;
+        }
+    }
+    urls
+}
+
+// Cells of a frame (already drawn by ratatui) that link to `url`
+pub(crate) struct Hyperlink {
+    pub url: String,
+    pub cells: Vec<(u16, u16, Cell)>,
+}
+
+// Draw the cells of `hyperlink` again as a link with OSC 8, in terminals that support it. This cannot be
+// done by ratatui itself, as it would count the escape sequences that open and close the link towards the
+// width of the cells
+pub(crate) fn draw_hyperlink<B: Backend + Write>(
+    backend: &mut B,
+    hyperlink: &Hyperlink,
+) -> io::Result<()> {
+    // Control characters would end the escape sequence early
+    let url: String = (hyperlink.url.chars())
+        .filter(|c| !c.is_control())
+        .collect();
+    write!(backend, "\x1b]8;;{}\x1b\\", url)?;
+    backend.draw((hyperlink.cells.iter()).map(|(x, y, cell)| (*x, *y, cell)))?;
+    write!(backend, "\x1b]8;;\x1b\\")
+}
+
+// Parse a date of a feed (e.g. an RSS `pubDate`), which by the specs is RFC 2822 (RSS) or RFC 3339 (Atom
+// and JSON Feed), but is often neither, e.g. with the other format, an invalid weekday, a "UTC" zone, or
+// without a time. `None` if the date cannot be made sense of
+pub(crate) fn parse_feed_date(date: &str) -> Option<DateTime<Local>> {
+    let date = date.trim();
+    // The weekday is redundant, and not always the right one (e.g. "Tue, 14 Oct 2026")
+    let without_weekday = match date.split_once(", ") {
+        Some((weekday, rest)) if weekday.chars().all(char::is_alphabetic) => rest,
+        _ => date,
+    };
+    // Zones that chrono does not know of, but that mean UTC
+    let date_with_offset = ["UTC", "Z"]
+        .iter()
+        .find_map(|zone| without_weekday.strip_suffix(zone))
+        .filter(|rest| rest.ends_with(' '))
+        .map(|rest| format!("{rest}+0000"));
+    let without_weekday = date_with_offset.as_deref().unwrap_or(without_weekday);
+
+    let parsed = DateTime::parse_from_rfc2822(date)
+        .or_else(|_| DateTime::parse_from_rfc3339(date))
+        .or_else(|_| DateTime::parse_from_rfc2822(without_weekday))
+        .ok()
+        .or_else(|| {
+            LOOSE_DATE_TIME_FMTS
+                .iter()
+                .find_map(|fmt| DateTime::parse_from_str(without_weekday, fmt).ok())
+        });
+    if let Some(parsed) = parsed {
+        return Some(parsed.into());
+    }
+
+    let naive = LOOSE_NAIVE_DATE_TIME_FMTS
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(without_weekday, fmt).ok())
+        .or_else(|| {
+            LOOSE_DATE_FMTS.iter().find_map(|fmt| {
+                NaiveDate::parse_from_str(without_weekday, fmt)
+                    .ok()
+                    .map(|date| date.and_time(NaiveTime::MIN))
+            })
+        })?;
+    Some(Utc.from_utc_datetime(&naive).into())
+}
+
+// Key identifying the page a URL points to, so that the same article linked from different feeds can be
+// told apart from others, e.g. "https://Example.com/a/?utm_source=rss#top" and "http://example.com/a" both
+// become "example.com/a". URLs that cannot be parsed are only trimmed
+pub(crate) fn normalize_url(url: &str) -> String {
+    let Ok(mut parsed) = Url::parse(url.trim()) else {
+        return url.trim().to_owned();
+    };
+    parsed.set_fragment(None);
+    let path = parsed.path().trim_end_matches('/').to_owned();
+    parsed.set_path(&path);
+    // Tracking parameters differ between feeds of the same site (e.g. `utm_campaign=category`)
+    let query: Vec<(String, String)> = parsed
+        .query_pairs()
+        .filter(|(key, _)| !key.starts_with("utm_"))
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+    parsed.set_query(None);
+    if !query.is_empty() {
+        parsed.query_pairs_mut().extend_pairs(query);
+    }
+    let normalized = parsed.as_str();
+    let normalized = normalized
+        .split_once("://")
+        .map_or(normalized, |(_, rest)| rest);
+    // The path of URLs without one (e.g. "https://example.com") is always "/"
+    normalized.trim_end_matches('/').to_owned()
+}
+
+// Query parameters stripped from URLs before they are opened or copied (see `strip_query_params`), which
+// only track where a visit came from
+pub(crate) const TRACKING_PARAMS: &[&str] = &[
+    "utm_*", "fbclid", "gclid", "dclid", "msclkid", "yclid", "mc_cid", "mc_eid", "_hsenc", "_hsmi",
+    "igshid",
+];
+
+// `url` without the query parameters named in `params`, where a name ending in `*` (e.g. "utm_*") is a
+// prefix of names. The other parameters are kept as they are (i.e. not re-encoded), and URLs that cannot
+// be parsed are left untouched
+pub(crate) fn strip_query_params(url: &str, params: &[String]) -> String {
+    let Ok(mut parsed) = Url::parse(url) else {
+        return url.to_owned();
+    };
+    let Some(query) = parsed.query() else {
+        return url.to_owned();
+    };
+    let is_stripped = |pair: &str| {
+        let Some((name, _)) = url::form_urlencoded::parse(pair.as_bytes()).next() else {
+            return false;
+        };
+        params.iter().any(|param| match param.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == param.as_str(),
+        })
+    };
+    let pairs: Vec<&str> = query.split('&').collect();
+    let kept_pairs: Vec<&str> = pairs
+        .iter()
+        .copied()
+        .filter(|pair| !is_stripped(pair))
+        .collect();
+    if kept_pairs.len() == pairs.len() {
+        return url.to_owned();
+    }
+    let kept_query = kept_pairs.join("&");
+    parsed.set_query((!kept_query.is_empty()).then_some(kept_query.as_str()));
+    parsed.into()
+}
+
+// Leading markup of a response body, lowercased and without any byte order mark or leading whitespace
+fn body_head(body: &[u8]) -> String {
+    let head = String::from_utf8_lossy(&body[..body.len().min(512)]);
+    head.trim_start_matches('\u{feff}')
+        .trim_start()
+        .to_ascii_lowercase()
+}
+
+// Sniff whether a response body is an HTML document rather than XML, based on its leading markup
+pub(crate) fn is_html_document(body: &[u8]) -> bool {
+    let head = body_head(body);
+    head.starts_with("<!doctype html") || head.starts_with("<html")
+}
+
+// Sniff whether a response body is meant to be an XML feed (RSS, RDF or Atom), based on its leading
+// markup, so that failing to parse it means the feed is malformed rather than not a feed at all
+pub(crate) fn is_xml_document(body: &[u8]) -> bool {
+    let head = body_head(body);
+    ["<?xml", "<rss", "<rdf:rdf", "<feed"]
+        .iter()
+        .any(|start| head.starts_with(start))
+}
+
+// The start of a response body on a single line, e.g. to show what was returned instead of a feed
+pub(crate) fn body_preview(body: &[u8]) -> String {
+    const MAX_CHARS: usize = 60;
+    let head = String::from_utf8_lossy(&body[..body.len().min(512)]);
+    let head = head
+        .trim_start_matches('\u{feff}')
+        .split_whitespace()
+        .join(" ");
+    match head.char_indices().nth(MAX_CHARS) {
+        Some((end, _)) => format!("{}…", &head[..end]),
+        None => head,
+    }
+}
+
+// Body of a response with its gzip compression undone, for servers that serve compressed feeds without
+// saying so with `Content-Encoding` (e.g. "feed.xml.gz"), which the HTTP client therefore leaves as is.
+// Bodies that are not gzip-compressed, or fail to decompress, are returned unchanged
+pub(crate) fn gunzip_body(body: &[u8]) -> Cow<'_, [u8]> {
+    const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+    if !body.starts_with(&GZIP_MAGIC) {
+        return Cow::Borrowed(body);
+    }
+    let mut decompressed = Vec::new();
+    match GzDecoder::new(body).read_to_end(&mut decompressed) {
+        Ok(_) => Cow::Owned(decompressed),
+        Err(_) => Cow::Borrowed(body),
+    }
+}
+
+// Hash of `value` that stays the same across builds, for hashes that are persisted (i.e. item ids in the
+// state file and names of cached responses), which `DefaultHasher` does not promise. This is SipHash-1-3
+// with zero keys, as `DefaultHasher` has been so far, so that previously persisted hashes still match
+pub(crate) fn stable_hash(value: impl Hash) -> u64 {
+    let mut hasher = SipHasher13::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+// Human-readable size in decimal units, e.g. "24.3 MB"
+pub(crate) fn format_size(bytes: u64) -> String {
+    let mut size = bytes as f64;
+    let mut unit = "B";
+    for larger_unit in ["kB", "MB", "GB", "TB"] {
+        if size < 1000.0 {
+            break;
+        }
+        size /= 1000.0;
+        unit = larger_unit;
+    }
+    match unit {
+        "B" => format!("{} B", bytes),
+        unit => format!("{:.1} {}", size, unit),
+    }
+}
+
+// Download `url` into `dir`, named after the last segment of its path (e.g. "episode-12.mp3") without
+// overwriting existing files (e.g. as "episode-12 (1).mp3"), returning the path of the downloaded file.
+// The file is only moved into place once fully downloaded, so that partial downloads are never mistaken
+// for complete ones
+pub(crate) async fn download_to_dir(
+    http_client: &Client,
+    url: &str,
+    dir: &Path,
+) -> Result<PathBuf, Box<dyn Error + Send + Sync>> {
+    let mut response = http_client.get(url).send().await?.error_for_status()?;
+
+    let file_name = Url::parse(url)
+        .ok()
+        .and_then(|url| {
+            url.path_segments()?
+                .rev()
+                .find(|segment| !segment.is_empty())
+                .map(str::to_owned)
+        })
+        .unwrap_or_else(|| "download".to_string());
+    let (stem, extension) = match file_name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, Some(extension)),
+        _ => (file_name.as_str(), None),
+    };
+    let part_path = |path: &Path| {
+        let mut part_path = path.as_os_str().to_owned();
+        part_path.push(".part");
+        PathBuf::from(part_path)
+    };
+    let path = (0..)
+        .map(|n| {
+            let stem = match n {
+                0 => stem.to_owned(),
+                n => format!("{} ({})", stem, n),
+            };
+            match extension {
+                Some(extension) => dir.join(format!("{}.{}", stem, extension)),
+                None => dir.join(stem),
+            }
+        })
+        .find(|path| !path.exists() && !part_path(path).exists())
+        .expect("some file name is unused");
+
+    tokio::fs::create_dir_all(dir).await?;
+    let part_path = part_path(&path);
+    let downloaded = async {
+        let mut file = tokio::fs::File::create(&part_path).await?;
+        while let Some(chunk) = response.chunk().await? {
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+        tokio::fs::rename(&part_path, &path).await?;
+        Ok::<_, Box<dyn Error + Send + Sync>>(())
+    }
+    .await;
+    if let Err(e) = downloaded {
+        tokio::fs::remove_file(&part_path).await.ok();
+        return Err(e);
+    }
+    Ok(path)
+}
+
+// `text` quoted as a single word for `sh`, e.g. to substitute a URL with `&` into a command
+pub(crate) fn shell_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "'\\''"))
+}
+
+// Run a shell command with `input` piped to its stdin, returning whatever it writes to stdout
+pub(crate) async fn pipe_through_command(cmd: &str, input: String) -> io::Result<String> {
+    let mut child = tokio::process::Command::new("sh")
+        .arg("-c")
+        .arg(cmd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // Write to stdin concurrently, otherwise commands that write output before consuming all of their
+    // input could deadlock on a full pipe. Dropping `stdin` once written signals EOF to the command
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let stdin_writer = tokio::spawn(async move { stdin.write_all(input.as_bytes()).await });
+
+    let output = child.wait_with_output().await?;
+    stdin_writer.await.map_err(io::Error::other)??;
+
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "`{}` exited with {}",
+            cmd, output.status
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
 #[macro_export]
 macro_rules! para_wrap {
     () => {{ ::ratatui::widgets::Paragraph::default().wrap(::ratatui::widgets::Wrap { trim: true }) }};
@@ -55,3 +771,77 @@ impl Throbber {
         tui_throbber.render(area, buf, &mut self._inner);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Parsed date as an RFC 3339 date in UTC, to compare regardless of the local timezone
+    fn parse_utc(date: &str) -> Option<String> {
+        parse_feed_date(date).map(|date| date.with_timezone(&Utc).to_rfc3339())
+    }
+
+    #[test]
+    fn strict_feed_dates_are_parsed() {
+        let expected = Some("2026-10-14T06:00:00+00:00".to_string());
+        assert_eq!(parse_utc("Wed, 14 Oct 2026 08:00:00 +0200"), expected);
+        assert_eq!(parse_utc("2026-10-14T08:00:00+02:00"), expected);
+    }
+
+    #[test]
+    fn loose_feed_dates_are_parsed() {
+        let expected = Some("2026-10-14T08:00:00+00:00".to_string());
+        for date in [
+            // Wrong weekday
+            "Mon, 14 Oct 2026 08:00:00 +0000",
+            // Zones that mean UTC
+            "Wed, 14 Oct 2026 08:00:00 UTC",
+            "Wed, 14 Oct 2026 08:00:00 Z",
+            // ISO dates with a space separator, or an offset without a colon
+            "2026-10-14 08:00:00 +0000",
+            "2026-10-14T10:00:00+0200",
+            // Without an offset, taken as UTC
+            "2026-10-14T08:00:00",
+            "2026-10-14 08:00",
+            // Full month names
+            "14 October 2026 08:00:00 +0000",
+            // Surrounding whitespace
+            "  2026-10-14T08:00:00Z  ",
+        ] {
+            assert_eq!(parse_utc(date), expected, "{:?}", date);
+        }
+    }
+
+    #[test]
+    fn dates_without_time_are_midnight_utc() {
+        for date in [
+            "2026-10-07",
+            "2026/10/07",
+            "7 October 2026",
+            "October 7, 2026",
+        ] {
+            let expected = Some("2026-10-07T00:00:00+00:00".to_string());
+            assert_eq!(parse_utc(date), expected, "{:?}", date);
+        }
+    }
+
+    #[test]
+    fn malformed_feed_dates_are_not_parsed() {
+        for date in [
+            "",
+            "yesterday",
+            "14/10/2026 8am",
+            "2026-13-45",
+            "Wed, 14 Oct",
+        ] {
+            assert_eq!(parse_utc(date), None, "{:?}", date);
+        }
+    }
+
+    // The hash of `DefaultHasher` up to now, under which ids and cache entries have been persisted
+    #[test]
+    fn stable_hash_matches_previously_persisted_hashes() {
+        let url = "https://example.com/feed.xml";
+        assert_eq!(stable_hash(url), 5333986857289017617);
+    }
+}